@@ -0,0 +1,35 @@
+use std::fs;
+
+use aoc::{day, days};
+use common::Part;
+use divan::{black_box, AllocProfiler, Bencher};
+
+// Report allocations and bytes per call next to each timing.
+#[global_allocator]
+static ALLOC: AllocProfiler = AllocProfiler::system();
+
+fn main() {
+    // Run registered benchmarks.
+    divan::main();
+}
+
+fn numbers() -> Vec<u8> {
+    days().iter().map(|d| d.number).collect()
+}
+
+fn bench_part(bencher: Bencher, number: u8, part: Part) {
+    let day = day(number).expect("should bench a registered day");
+    let input = fs::read_to_string(day.input_path())
+        .expect("should have the day's input.txt");
+    bencher.bench(|| day.solve(black_box(&input), part).unwrap());
+}
+
+#[divan::bench(args = numbers())]
+fn part1(bencher: Bencher, day: u8) {
+    bench_part(bencher, day, Part::Part1);
+}
+
+#[divan::bench(args = numbers())]
+fn part2(bencher: Bencher, day: u8) {
+    bench_part(bencher, day, Part::Part2);
+}
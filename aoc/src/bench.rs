@@ -0,0 +1,205 @@
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use std::{fs, io};
+
+use common::Part;
+use serde::{Deserialize, Serialize};
+
+use crate::{memory, Day};
+
+/// Timings of one part on its real input.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sample {
+    pub day: u8,
+    pub part: u8,
+    pub iterations: u32,
+    pub median_ns: u64,
+    pub min_ns: u64,
+    /// Allocations per call, when the counting allocator is installed.
+    /// Missing from reports saved before they were counted.
+    #[serde(default)]
+    pub allocations: Option<u64>,
+    /// Bytes allocated per call, freed or not.
+    #[serde(default)]
+    pub allocated_bytes: Option<u64>,
+}
+
+impl Sample {
+    pub fn label(&self) -> String {
+        format!("day{:<2} part{}", self.day, self.part)
+    }
+}
+
+/// A benchmark run, as saved to and loaded from JSON.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Report {
+    pub samples: Vec<Sample>,
+}
+
+impl Report {
+    pub fn load(path: &Path) -> io::Result<Report> {
+        let json = fs::read_to_string(path)?;
+        serde_json::from_str(&json)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .expect("should serialize a report");
+        fs::write(path, json + "\n")
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Sample> {
+        self.samples.iter().find(|s| s.day == day && s.part == part)
+    }
+
+    /// Samples whose median got slower than the baseline by more than
+    /// `threshold` percent. Parts missing from the baseline are skipped.
+    pub fn regressions(
+        &self,
+        baseline: &Report,
+        threshold: f64,
+    ) -> Vec<Regression> {
+        self.samples
+            .iter()
+            .filter_map(|sample| {
+                let before = baseline.get(sample.day, sample.part)?;
+                let change =
+                    percent_change(before.median_ns, sample.median_ns);
+                (change > threshold).then(|| Regression {
+                    sample: sample.clone(),
+                    baseline_ns: before.median_ns,
+                    change,
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    pub sample: Sample,
+    pub baseline_ns: u64,
+    pub change: f64,
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {:.2?} -> {:.2?} (+{:.1}%)",
+            self.sample.label(),
+            Duration::from_nanos(self.baseline_ns),
+            Duration::from_nanos(self.sample.median_ns),
+            self.change
+        )
+    }
+}
+
+pub fn percent_change(before: u64, after: u64) -> f64 {
+    if before == 0 {
+        return 0.0;
+    }
+    (after as f64 - before as f64) / before as f64 * 100.0
+}
+
+/// Solves `part` repeatedly until `budget` is spent (at least three times)
+/// and keeps the median and fastest run, then once more to count its
+/// allocations. The input should be well-formed.
+pub fn measure(
+    day: &Day,
+    part: Part,
+    input: &str,
+    budget: Duration,
+) -> Sample {
+    let mut times = vec![];
+    let start = Instant::now();
+    while times.len() < 3 || start.elapsed() < budget {
+        let run = Instant::now();
+        let answer = day.solve(std::hint::black_box(input), part);
+        std::hint::black_box(answer.expect("should bench a valid input"));
+        times.push(run.elapsed().as_nanos() as u64);
+    }
+    times.sort_unstable();
+    let (_, usage) = memory::usage(|| day.solve(input, part));
+    Sample {
+        day: day.number,
+        part: match part {
+            Part::Part1 => 1,
+            Part::Part2 => 2,
+        },
+        iterations: times.len() as u32,
+        median_ns: times[times.len() / 2],
+        min_ns: times[0],
+        allocations: usage.map(|u| u.allocations as u64),
+        allocated_bytes: usage.map(|u| u.allocated as u64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(day: u8, part: u8, median_ns: u64) -> Sample {
+        Sample {
+            day,
+            part,
+            iterations: 10,
+            median_ns,
+            min_ns: median_ns,
+            allocations: Some(100),
+            allocated_bytes: Some(4096),
+        }
+    }
+
+    #[test]
+    fn test_regressions() {
+        let baseline = Report {
+            samples: vec![sample(1, 1, 1000), sample(1, 2, 1000)],
+        };
+        let current = Report {
+            samples: vec![
+                sample(1, 1, 1050),
+                sample(1, 2, 1200),
+                sample(2, 1, 9000),
+            ],
+        };
+        let regressions = current.regressions(&baseline, 10.0);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].sample, sample(1, 2, 1200));
+        assert_eq!(regressions[0].change, 20.0);
+        assert!(current.regressions(&baseline, 25.0).is_empty());
+    }
+
+    #[test]
+    fn test_report_round_trip() {
+        let report = Report {
+            samples: vec![sample(5, 2, 123)],
+        };
+        let path = std::env::temp_dir()
+            .join(format!("aoc-bench-{}.json", std::process::id()));
+        report.save(&path).unwrap();
+        assert_eq!(Report::load(&path).unwrap().samples, report.samples);
+        fs::write(
+            &path,
+            r#"{"samples": [{"day": 5, "part": 2, "iterations": 10,
+                "median_ns": 123, "min_ns": 100}]}"#,
+        )
+        .unwrap();
+        let old = Report::load(&path).unwrap();
+        assert_eq!(old.samples[0].allocations, None);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_measure() {
+        let day = crate::day(15).unwrap();
+        let sample = measure(&day, Part::Part1, "rn=1,cm-", Duration::ZERO);
+        assert_eq!((sample.day, sample.part, sample.iterations), (15, 1, 3));
+        assert!(sample.min_ns <= sample.median_ns);
+        // The lib's tests run with the counting allocator installed.
+        assert!(sample.allocations.is_some_and(|n| n > 0));
+        assert!(sample.allocated_bytes.is_some_and(|n| n > 0));
+    }
+}
@@ -0,0 +1,287 @@
+//! Solves every part at once on a thread pool and reports how long each one
+//! took, how much memory it peaked at and what it answered.
+
+use std::cmp::Reverse;
+use std::fmt::Write;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use common::error::SolveError;
+use common::{Answer, Part};
+use rayon::prelude::*;
+use rayon::{ThreadPoolBuildError, ThreadPoolBuilder};
+
+use crate::memory::{self, format_bytes};
+use crate::variants::{self, Variant};
+use crate::Day;
+
+/// One part to solve on a day's input, with one of its other algorithms
+/// rather than the day's own if `variant` is set.
+pub struct Job<'a> {
+    pub day: &'a Day,
+    pub part: Part,
+    pub variant: Option<Variant>,
+    pub input: &'a str,
+}
+
+impl Job<'_> {
+    pub fn label(&self) -> String {
+        match self.variant {
+            Some(variant) => format!(
+                "day{:<2} {} [{}]",
+                self.day.number, self.part, variant.name
+            ),
+            None => format!("day{:<2} {}", self.day.number, self.part),
+        }
+    }
+
+    fn solve(&self) -> Result<Answer, SolveError> {
+        match self.variant {
+            Some(variant) => variant.solve(self.input),
+            None => self.day.solve(self.input, self.part),
+        }
+    }
+}
+
+/// Both parts of each day on its input and, with `variants`, every other
+/// algorithm registered for them too.
+pub fn jobs(inputs: &[(Day, String)], variants: bool) -> Vec<Job<'_>> {
+    let mut jobs = vec![];
+    for (day, input) in inputs {
+        for part in [Part::Part1, Part::Part2] {
+            jobs.push(Job {
+                day,
+                part,
+                variant: None,
+                input,
+            });
+            if variants {
+                // The first variant is the day's own algorithm.
+                for variant in variants::of(day, part).into_iter().skip(1) {
+                    jobs.push(Job {
+                        day,
+                        part,
+                        variant: Some(variant),
+                        input,
+                    });
+                }
+            }
+        }
+    }
+    jobs
+}
+
+/// How one job went.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timing {
+    pub label: String,
+    pub answer: Result<Answer, String>,
+    pub elapsed: Duration,
+    /// `None` unless the counting allocator is installed.
+    pub peak_bytes: Option<usize>,
+}
+
+impl Timing {
+    pub fn failed(&self) -> bool {
+        self.answer.is_err()
+    }
+}
+
+/// Every job's timing, slowest first, and how long they took together.
+#[derive(Debug)]
+pub struct Calendar {
+    pub timings: Vec<Timing>,
+    pub wall: Duration,
+    pub threads: usize,
+}
+
+/// Runs `jobs` on a pool of `threads` threads, one per core by default.
+/// Each job runs on a single thread, so its peak memory is its own.
+pub fn run(
+    jobs: &[Job],
+    threads: Option<usize>,
+) -> Result<Calendar, ThreadPoolBuildError> {
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(threads.unwrap_or(0))
+        .build()?;
+    let start = Instant::now();
+    let mut timings: Vec<Timing> =
+        pool.install(|| jobs.par_iter().map(time).collect());
+    let wall = start.elapsed();
+    timings.sort_by_key(|t| Reverse(t.elapsed));
+    Ok(Calendar {
+        timings,
+        wall,
+        threads: pool.current_num_threads(),
+    })
+}
+
+fn time(job: &Job) -> Timing {
+    let start = Instant::now();
+    let (answer, usage) =
+        memory::usage(|| catch_unwind(AssertUnwindSafe(|| job.solve())));
+    let elapsed = start.elapsed();
+    Timing {
+        label: job.label(),
+        answer: match answer {
+            Ok(Ok(answer)) => Ok(answer),
+            Ok(Err(SolveError::Malformed(err))) => {
+                Err(format!("malformed input: {}", err.message()))
+            }
+            Ok(Err(SolveError::NoAnswer(message))) => {
+                Err(format!("no answer: {}", message))
+            }
+            Err(_) => Err("panicked".to_string()),
+        },
+        elapsed,
+        peak_bytes: usage.map(|u| u.peak),
+    }
+}
+
+impl Calendar {
+    /// The time spent solving, summed over every job.
+    pub fn total(&self) -> Duration {
+        self.timings.iter().map(|t| t.elapsed).sum()
+    }
+
+    pub fn over_budget(&self, budget: Duration) -> usize {
+        self.timings.iter().filter(|t| t.elapsed > budget).count()
+    }
+
+    /// One row per job and a total, flagging the jobs slower than `budget`,
+    /// in red with `color`.
+    pub fn table(&self, budget: Duration, color: bool) -> String {
+        let width = self
+            .timings
+            .iter()
+            .map(|t| t.label.len())
+            .max()
+            .unwrap_or(0)
+            .max("total".len());
+        let mut table = String::new();
+        for timing in &self.timings {
+            let answer = match &timing.answer {
+                Ok(answer) => answer.to_string(),
+                Err(err) => err.clone(),
+            };
+            let mut row = format!(
+                "{:<width$}  {:<20} {:>10.2?} {:>10}",
+                timing.label,
+                answer,
+                timing.elapsed,
+                timing.peak_bytes.map_or("-".to_string(), format_bytes),
+            );
+            if timing.elapsed > budget {
+                row += "  over budget";
+                if color {
+                    row = format!("\x1b[31m{row}\x1b[0m");
+                }
+            }
+            writeln!(table, "{row}").unwrap();
+        }
+        let peak = self.timings.iter().filter_map(|t| t.peak_bytes).max();
+        writeln!(
+            table,
+            "{:<width$}  {:<20} {:>10.2?} {:>10}",
+            "total",
+            format!("{} parts", self.timings.len()),
+            self.total(),
+            peak.map_or("-".to_string(), format_bytes),
+        )
+        .unwrap();
+        write!(
+            table,
+            "{:.2?} wall clock on {} threads, {} over the {:.2?} budget",
+            self.wall,
+            self.threads,
+            self.over_budget(budget),
+            budget
+        )
+        .unwrap();
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day;
+
+    #[test]
+    fn test_run() {
+        let inputs = vec![
+            (
+                day(15).unwrap(),
+                "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7"
+                    .to_string(),
+            ),
+            (day(2).unwrap(), "Game 1: x".to_string()),
+        ];
+        assert_eq!(jobs(&inputs, false).len(), 4);
+        let jobs = jobs(&inputs, true);
+        assert_eq!(jobs.len(), 4);
+        let calendar = run(&jobs, Some(2)).unwrap();
+        assert_eq!(calendar.threads, 2);
+        assert_eq!(calendar.timings.len(), 4);
+        assert!(calendar
+            .timings
+            .windows(2)
+            .all(|pair| pair[0].elapsed >= pair[1].elapsed));
+        let day15 = calendar
+            .timings
+            .iter()
+            .find(|t| t.label == "day15 part1")
+            .unwrap();
+        assert_eq!(day15.answer, Ok(Answer::from(1320u32)));
+        assert_eq!(calendar.timings.iter().filter(|t| t.failed()).count(), 2);
+    }
+
+    #[test]
+    fn test_variant_jobs() {
+        let inputs = vec![(day(5).unwrap(), String::new())];
+        let labels: Vec<String> =
+            jobs(&inputs, true).iter().map(Job::label).collect();
+        assert_eq!(
+            labels,
+            ["day5  part1", "day5  part2", "day5  part2 [brute-force]"]
+        );
+    }
+
+    fn timing(label: &str, millis: u64) -> Timing {
+        Timing {
+            label: label.to_string(),
+            answer: Ok(Answer::from(millis)),
+            elapsed: Duration::from_millis(millis),
+            peak_bytes: Some(2048),
+        }
+    }
+
+    #[test]
+    fn test_table() {
+        let calendar = Calendar {
+            timings: vec![
+                timing("day5  part2 [brute-force]", 3000),
+                timing("day1  part1", 2),
+            ],
+            wall: Duration::from_millis(3001),
+            threads: 4,
+        };
+        assert_eq!(calendar.total(), Duration::from_millis(3002));
+        assert_eq!(calendar.over_budget(Duration::from_secs(1)), 1);
+        let table = calendar.table(Duration::from_secs(1), false);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("day5  part2 [brute-force]  3000 "));
+        assert!(lines[0].ends_with("2.0 KiB  over budget"));
+        assert!(lines[1].starts_with("day1  part1                2 "));
+        assert!(lines[1].ends_with("2.0 KiB"));
+        assert!(lines[2].starts_with("total                      2 parts "));
+        assert!(lines[2].contains("3.00s"));
+        assert_eq!(
+            lines[3],
+            "3.00s wall clock on 4 threads, 1 over the 1.00s budget"
+        );
+        let colored = calendar.table(Duration::from_secs(1), true);
+        assert!(colored.starts_with("\x1b[31mday5"));
+    }
+}
@@ -0,0 +1,400 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use common::{Answer, Part};
+use toml::{Table, Value};
+
+use crate::{answer_from, Day};
+
+/// What a puzzle page gives away: its example blocks, and for each part the
+/// highlighted answer together with the example it belongs to.
+#[derive(Debug, Default, PartialEq)]
+pub struct Extracted {
+    pub examples: Vec<String>,
+    pub part1: Option<(usize, String)>,
+    pub part2: Option<(usize, String)>,
+}
+
+/// Pulls the `<pre><code>` examples and the `<code><em>` answers out of a
+/// puzzle page. Each part lives in its own `<article>`; its answer is the
+/// last highlighted code there and is paired with the last example shown
+/// so far, since part two usually reuses part one's example.
+pub fn extract(html: &str) -> Extracted {
+    let mut extracted = Extracted::default();
+    for (i, article) in sections(html, "<article", "</article>")
+        .into_iter()
+        .take(2)
+        .enumerate()
+    {
+        extracted.examples.extend(
+            sections(article, "<pre><code>", "</code></pre>")
+                .into_iter()
+                .map(text),
+        );
+        let answer = sections(article, "<code><em>", "</em></code>")
+            .last()
+            .map(|s| text(s));
+        let example = extracted.examples.len().checked_sub(1);
+        let part = example.zip(answer);
+        if i == 0 {
+            extracted.part1 = part;
+        } else {
+            extracted.part2 = part;
+        }
+    }
+    extracted
+}
+
+/// The contents between every `open` (up to the end of its tag) and the
+/// following `close`.
+fn sections<'a>(html: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut found = vec![];
+    let mut rest = html;
+    while let Some(start) = rest.find(open) {
+        rest = &rest[start + open.len()..];
+        if !open.ends_with('>') {
+            let Some(end) = rest.find('>') else { break };
+            rest = &rest[end + 1..];
+        }
+        let Some(end) = rest.find(close) else { break };
+        found.push(&rest[..end]);
+        rest = &rest[end + close.len()..];
+    }
+    found
+}
+
+/// Drops nested tags such as `<em>` and decodes the entities the site uses.
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+pub fn examples_dir(day: &Day) -> PathBuf {
+    day.dir().join("examples")
+}
+
+/// The day's `exampleN.txt` files, in order of `N`.
+pub fn files(day: &Day) -> Vec<PathBuf> {
+    numbered(&examples_dir(day))
+        .into_iter()
+        .map(|(_, path)| path)
+        .collect()
+}
+
+/// The `exampleN.txt` files in `dir` with their `N`, in order.
+fn numbered(dir: &Path) -> Vec<(u32, PathBuf)> {
+    let mut files: Vec<(u32, PathBuf)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_name()?.to_str()?;
+            let n = name.strip_prefix("example")?.strip_suffix(".txt")?;
+            Some((n.parse().ok()?, path))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Writes the examples next to an `expected.toml` that names the example
+/// and answer for each part, keeping the cases already listed there.
+pub fn write(day: &Day, extracted: &Extracted) -> io::Result<()> {
+    write_to(&examples_dir(day), extracted)
+}
+
+/// Writes each example not already in `dir` as an `exampleN.txt` numbered
+/// after the existing ones, so examples added by hand are never
+/// overwritten, then merges the answers into `expected.toml`.
+fn write_to(dir: &Path, extracted: &Extracted) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let existing = numbered(dir);
+    let mut next = existing.last().map_or(1, |(n, _)| n + 1);
+    let mut inputs = vec![];
+    for example in &extracted.examples {
+        let same = existing.iter().find(|(_, path)| {
+            fs::read_to_string(path).is_ok_and(|text| text == *example)
+        });
+        let input = match same {
+            Some((_, path)) => path.clone(),
+            None => {
+                let path = dir.join(format!("example{}.txt", next));
+                fs::write(&path, example)?;
+                next += 1;
+                path
+            }
+        };
+        inputs.push(input);
+    }
+    let cases = merge(load_from(dir)?, extracted, &inputs);
+    fs::write(dir.join("expected.toml"), render(&cases, dir))
+}
+
+/// Adds the extracted answers to `cases`, replacing the answer of a case
+/// for the same part and example, `inputs` being where each extracted
+/// example was written. The page only highlights one answer per part, so
+/// cases added by hand for the other examples stay.
+fn merge(
+    mut cases: Vec<Case>,
+    extracted: &Extracted,
+    inputs: &[PathBuf],
+) -> Vec<Case> {
+    let parts = [
+        (Part::Part1, &extracted.part1),
+        (Part::Part2, &extracted.part2),
+    ];
+    for (part, found) in parts {
+        let Some((example, answer)) = found else {
+            continue;
+        };
+        let Some(input) = inputs.get(*example) else {
+            continue;
+        };
+        let case = Case {
+            part,
+            input: input.clone(),
+            answer: match answer.parse::<i64>() {
+                Ok(n) => Answer::from(n),
+                Err(_) => Answer::from(answer.as_str()),
+            },
+        };
+        match cases
+            .iter_mut()
+            .find(|c| c.part == part && c.input == case.input)
+        {
+            Some(existing) => *existing = case,
+            None => cases.push(case),
+        }
+    }
+    cases.sort_by_key(|case| case.part == Part::Part2);
+    cases
+}
+
+fn render(cases: &[Case], dir: &Path) -> String {
+    let mut expected = String::from(
+        "# Examples and their answers, checked by `cargo test -p aoc`.\n\
+         # `aoc examples` adds the ones highlighted on the puzzle page and\n\
+         # keeps the rest, so more cases can be listed by hand.\n",
+    );
+    for case in cases {
+        let answer = match &case.answer {
+            Answer::Number(n) => n.to_string(),
+            Answer::Text(s) => format!("{:?}", s),
+        };
+        let input = case.input.strip_prefix(dir).unwrap_or(&case.input);
+        write!(
+            expected,
+            "\n[[{}]]\ninput = \"{}\"\nanswer = {}\n",
+            case.part,
+            input.display(),
+            answer
+        )
+        .expect("should write to a string");
+    }
+    expected
+}
+
+/// An example input with the answer the puzzle page gives for it.
+#[derive(Debug, PartialEq)]
+pub struct Case {
+    pub part: Part,
+    pub input: PathBuf,
+    pub answer: Answer,
+}
+
+/// The cases listed in the day's `examples/expected.toml`, if it has one.
+pub fn load(day: &Day) -> io::Result<Vec<Case>> {
+    load_from(&examples_dir(day))
+}
+
+fn load_from(dir: &Path) -> io::Result<Vec<Case>> {
+    let expected = match fs::read_to_string(dir.join("expected.toml")) {
+        Ok(expected) => expected,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Ok(vec![])
+        }
+        Err(err) => return Err(err),
+    };
+    let expected = expected
+        .parse::<Table>()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let mut cases = vec![];
+    for part in [Part::Part1, Part::Part2] {
+        let entries = match expected.get(&part.to_string()) {
+            Some(Value::Array(entries)) => entries.as_slice(),
+            _ => &[],
+        };
+        for entry in entries {
+            let input = entry.get("input").and_then(Value::as_str);
+            let answer = entry.get("answer").map(|a| answer_from(part, a));
+            let (Some(input), Some(answer)) = (input, answer) else {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("each {} entry needs an input and answer", part),
+                ));
+            };
+            cases.push(Case {
+                part,
+                input: dir.join(input),
+                answer: answer?,
+            });
+        }
+    }
+    Ok(cases)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn test_extract() {
+        let html = indoc! {r#"
+            <main>
+            <article class="day-desc"><h2>--- Day 9: Mirage ---</h2>
+            <p>For example:</p>
+            <pre><code>0 3 6
+            1 &lt;3 <em>5</em>
+            </code></pre>
+            <p>The sum is <code>1 + 2</code>, or <code><em>114</em></code>.</p>
+            </article>
+            <p>Your puzzle answer was <code>1684566095</code>.</p>
+            <article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+            <p>Going backwards gives <code><em>2</em></code>.</p>
+            </article>
+            </main>
+            "#};
+        assert_eq!(
+            extract(html),
+            Extracted {
+                examples: vec!["0 3 6\n1 <3 5\n".to_string()],
+                part1: Some((0, "114".to_string())),
+                part2: Some((0, "2".to_string())),
+            }
+        );
+    }
+
+    #[test]
+    fn test_extract_without_examples() {
+        assert_eq!(
+            extract("<article><p><code><em>1</em></code></p></article>"),
+            Extracted::default()
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let day = crate::day(8).unwrap();
+        let dir = examples_dir(&day);
+        let extracted = Extracted {
+            examples: vec![],
+            part1: Some((0, "3".to_string())),
+            part2: Some((1, "abc".to_string())),
+        };
+        let inputs = [dir.join("example1.txt"), dir.join("example4.txt")];
+        let cases = merge(load(&day).unwrap(), &extracted, &inputs);
+        let answers: Vec<(Part, Answer)> =
+            cases.iter().map(|c| (c.part, c.answer.clone())).collect();
+        assert_eq!(
+            answers,
+            [
+                (Part::Part1, Answer::from(3u32)),
+                (Part::Part1, Answer::from(6u32)),
+                (Part::Part2, Answer::from(6u32)),
+                (Part::Part2, Answer::from("abc")),
+            ]
+        );
+        let expected = render(&cases, &dir);
+        assert!(expected.contains("[[part1]]\ninput = \"example2.txt\""));
+        assert!(
+            expected.ends_with("input = \"example4.txt\"\nanswer = \"abc\"\n")
+        );
+    }
+
+    #[test]
+    fn test_write_keeps_existing_examples() {
+        let dir = std::env::temp_dir()
+            .join(format!("aoc-examples-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("example1.txt"), "by hand\n").unwrap();
+        fs::write(dir.join("example2.txt"), "1 2\n").unwrap();
+        let extracted = Extracted {
+            examples: vec!["1 2\n".to_string(), "3 4\n".to_string()],
+            part1: Some((0, "3".to_string())),
+            part2: Some((1, "7".to_string())),
+        };
+        write_to(&dir, &extracted).unwrap();
+
+        let names: Vec<String> = numbered(&dir)
+            .into_iter()
+            .map(|(_, path)| {
+                path.strip_prefix(&dir).unwrap().display().to_string()
+            })
+            .collect();
+        assert_eq!(names, ["example1.txt", "example2.txt", "example3.txt"]);
+        let read = |name| fs::read_to_string(dir.join(name)).unwrap();
+        assert_eq!(read("example1.txt"), "by hand\n");
+        assert_eq!(read("example3.txt"), "3 4\n");
+        let inputs: Vec<(Part, PathBuf)> = load_from(&dir)
+            .unwrap()
+            .into_iter()
+            .map(|case| (case.part, case.input))
+            .collect();
+        assert_eq!(
+            inputs,
+            [
+                (Part::Part1, dir.join("example2.txt")),
+                (Part::Part2, dir.join("example3.txt")),
+            ]
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_render() {
+        for day in crate::days() {
+            let dir = examples_dir(&day);
+            let cases = load(&day).unwrap();
+            let expected = fs::read_to_string(dir.join("expected.toml"));
+            assert_eq!(
+                render(&cases, &dir),
+                expected.unwrap(),
+                "{}",
+                day.number
+            );
+        }
+    }
+
+    #[test]
+    fn test_load() {
+        let day = crate::day(5).unwrap();
+        let cases = load(&day).unwrap();
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].part, Part::Part1);
+        assert_eq!(cases[0].answer, Answer::from(35u32));
+        assert!(cases[0].input.exists());
+    }
+
+    #[test]
+    fn test_files() {
+        let files = files(&crate::day(10).unwrap());
+        assert_eq!(files.len(), 5);
+        assert!(files[4].ends_with("day10/examples/example5.txt"));
+    }
+}
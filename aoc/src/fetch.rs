@@ -0,0 +1,200 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{fs, io};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/zh0uquan/adventofcode2023 (aoc fetch)";
+
+/// Input bodies the site serves with a 200 when the session cookie is
+/// missing or expired.
+const LOGGED_OUT_MARKERS: [&str; 2] =
+    ["Please log in", "Puzzle inputs differ by user"];
+
+#[derive(Debug)]
+pub enum FetchError {
+    MissingSession,
+    Status(u16),
+    LoggedOut,
+    Http(String),
+    Io(io::Error),
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::MissingSession => {
+                write!(f, "SESSION is not set, copy it from the site cookie")
+            }
+            FetchError::Status(code) => {
+                write!(f, "server answered with status {}", code)
+            }
+            FetchError::LoggedOut => {
+                write!(f, "server asked to log in, SESSION may have expired")
+            }
+            FetchError::Http(err) => write!(f, "request failed: {}", err),
+            FetchError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<io::Error> for FetchError {
+    fn from(err: io::Error) -> Self {
+        FetchError::Io(err)
+    }
+}
+
+/// Downloads puzzle inputs into an on-disk cache, so each input is only
+/// requested once, and spaces requests at least `min_interval` apart, even
+/// across separate runs.
+pub struct Fetcher {
+    base_url: String,
+    session: Option<String>,
+    year: u16,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+}
+
+impl Fetcher {
+    pub fn new(
+        base_url: &str,
+        session: Option<String>,
+        year: u16,
+        cache_dir: &Path,
+    ) -> Fetcher {
+        Fetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            year,
+            cache_dir: cache_dir.to_path_buf(),
+            min_interval: Duration::from_secs(5),
+        }
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Fetcher {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub fn cache_path(&self, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(self.year.to_string())
+            .join(format!("day{}.txt", day))
+    }
+
+    pub fn puzzle_cache_path(&self, day: u8) -> PathBuf {
+        self.cache_path(day).with_extension("html")
+    }
+
+    /// The input for `day`, from the cache unless `force` is set.
+    pub fn input(&self, day: u8, force: bool) -> Result<String, FetchError> {
+        let cached = self.cache_path(day);
+        if self.session.is_none() && (force || !cached.exists()) {
+            return Err(FetchError::MissingSession);
+        }
+        let path = format!("{}/day/{}/input", self.year, day);
+        self.cached(&cached, &path, force, &LOGGED_OUT_MARKERS)
+    }
+
+    /// The puzzle description page for `day`, from the cache unless `force`
+    /// is set. Logged out pages still describe part one; part two only shows
+    /// up once part one is solved, so refetch with `force` after that.
+    /// Without a session the page is requested logged out.
+    pub fn puzzle(&self, day: u8, force: bool) -> Result<String, FetchError> {
+        let path = format!("{}/day/{}", self.year, day);
+        self.cached(&self.puzzle_cache_path(day), &path, force, &[])
+    }
+
+    fn cached(
+        &self,
+        cached: &Path,
+        path: &str,
+        force: bool,
+        logged_out_markers: &[&str],
+    ) -> Result<String, FetchError> {
+        if !force {
+            if let Ok(body) = fs::read_to_string(cached) {
+                return Ok(body);
+            }
+        }
+        let body = self.get(path)?;
+        if logged_out_markers.iter().any(|m| body.contains(m)) {
+            return Err(FetchError::LoggedOut);
+        }
+        write_atomically(cached, &body)?;
+        Ok(body)
+    }
+
+    /// Fetches the input for `day` and writes it to `dest`. `dest` is left
+    /// untouched when the download fails.
+    pub fn install(
+        &self,
+        day: u8,
+        dest: &Path,
+        force: bool,
+    ) -> Result<(), FetchError> {
+        let input = self.input(day, force)?;
+        if fs::read_to_string(dest).ok().as_deref() != Some(&input) {
+            write_atomically(dest, &input)?;
+        }
+        Ok(())
+    }
+
+    /// Sends the session cookie when there is one.
+    fn get(&self, path: &str) -> Result<String, FetchError> {
+        self.wait_for_turn()?;
+
+        let url = format!("{}/{}", self.base_url, path);
+        let mut request = ureq::get(&url).set("User-Agent", USER_AGENT);
+        if let Some(session) = &self.session {
+            request = request.set("Cookie", &format!("session={}", session));
+        }
+        let response = request.call();
+        let body = match response {
+            Ok(response) if response.status() == 200 => response
+                .into_string()
+                .map_err(|e| FetchError::Http(e.to_string()))?,
+            Ok(response) => return Err(FetchError::Status(response.status())),
+            Err(ureq::Error::Status(code, _)) => {
+                return Err(FetchError::Status(code))
+            }
+            Err(err) => return Err(FetchError::Http(err.to_string())),
+        };
+        Ok(body)
+    }
+
+    /// Sleeps until `min_interval` has passed since the last request, then
+    /// records this one.
+    fn wait_for_turn(&self) -> io::Result<()> {
+        let stamp = self.cache_dir.join(".last-request");
+        let now = SystemTime::now();
+        let last = fs::read_to_string(&stamp)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        if let Some(last) = last {
+            let since = now.duration_since(last).unwrap_or_default();
+            if since < self.min_interval {
+                thread::sleep(self.min_interval - since);
+            }
+        }
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        write_atomically(&stamp, &millis.to_string())
+    }
+}
+
+fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, contents)?;
+    fs::rename(tmp, path)
+}
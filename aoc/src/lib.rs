@@ -0,0 +1,148 @@
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use common::error::SolveError;
+use common::{Answer, Part, Solution};
+use toml::{Table, Value};
+
+pub mod bench;
+pub mod calendar;
+pub mod examples;
+pub mod fetch;
+pub mod memory;
+pub mod scaffold;
+pub mod serve;
+pub mod variants;
+pub mod view;
+pub mod watch;
+
+/// A puzzle day wired into the runner through its `Solution` impl.
+pub struct Day {
+    pub number: u8,
+    solve: fn(&str, Part) -> Result<Answer, SolveError>,
+}
+
+impl Day {
+    pub fn of<S: Solution>() -> Day {
+        Day {
+            number: S::DAY,
+            solve: S::solve,
+        }
+    }
+
+    pub fn solve(
+        &self,
+        input: &str,
+        part: Part,
+    ) -> Result<Answer, SolveError> {
+        (self.solve)(input, part)
+    }
+
+    pub fn dir(&self) -> PathBuf {
+        workspace_root().join(format!("day{}", self.number))
+    }
+
+    pub fn input_path(&self) -> PathBuf {
+        self.dir().join("src").join("input.txt")
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        self.dir().join("answers.toml")
+    }
+
+    /// The accepted answer for `part` on the real input, as recorded in the
+    /// day's answers.toml.
+    pub fn expected(&self, part: Part) -> io::Result<Option<Answer>> {
+        let answers = fs::read_to_string(self.answers_path())?
+            .parse::<Table>()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        answers
+            .get(&part.to_string())
+            .map(|value| answer_from(part, value))
+            .transpose()
+    }
+}
+
+/// Reads an answer recorded in a toml file as a number or string.
+fn answer_from(part: Part, value: &Value) -> io::Result<Answer> {
+    match value {
+        Value::Integer(n) => Ok(Answer::from(*n)),
+        Value::String(s) => Ok(Answer::from(s.as_str())),
+        value => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} should be a number or string: {}", part, value),
+        )),
+    }
+}
+
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("runner should live inside the workspace")
+}
+
+pub fn days() -> Vec<Day> {
+    vec![
+        Day::of::<day1::Day1>(),
+        Day::of::<day2::Day2>(),
+        Day::of::<day3::Day3>(),
+        Day::of::<day4::Day4>(),
+        Day::of::<day5::Day5>(),
+        Day::of::<day6::Day6>(),
+        Day::of::<day7::Day7>(),
+        Day::of::<day8::Day8>(),
+        Day::of::<day9::Day9>(),
+        Day::of::<day10::Day10>(),
+        Day::of::<day11::Day11>(),
+        Day::of::<day12::Day12>(),
+        Day::of::<day13::Day13>(),
+        Day::of::<day14::Day14>(),
+        Day::of::<day15::Day15>(),
+        Day::of::<day16::Day16>(),
+    ]
+}
+
+pub fn day(number: u8) -> Option<Day> {
+    days().into_iter().find(|d| d.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days() {
+        let numbers: Vec<u8> = days().iter().map(|d| d.number).collect();
+        let count = numbers.len() as u8;
+        assert_eq!(numbers, (1..=count).collect::<Vec<u8>>());
+        assert!(day(count + 1).is_none());
+    }
+
+    #[test]
+    fn test_solve() {
+        let day = day(15).unwrap();
+        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+        assert_eq!(day.solve(input, Part::Part1), Ok(Answer::from(1320u32)));
+        assert_eq!(day.solve(input, Part::Part2), Ok(Answer::from(145u32)));
+        let err = super::day(2).unwrap().solve("Game 1: x", Part::Part1);
+        assert!(
+            matches!(err, Err(SolveError::Malformed(err)) if err.line() == 1)
+        );
+    }
+
+    #[test]
+    fn test_input_path() {
+        let day = day(5).unwrap();
+        assert!(day.input_path().ends_with("day5/src/input.txt"));
+        assert!(day.input_path().exists());
+    }
+
+    #[test]
+    fn test_expected() {
+        let day = day(8).unwrap();
+        assert_eq!(
+            day.expected(Part::Part2).unwrap(),
+            Some(Answer::from(23977527174353u64))
+        );
+    }
+}
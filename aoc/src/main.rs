@@ -0,0 +1,825 @@
+use std::fs;
+use std::io::IsTerminal;
+use std::panic::catch_unwind;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc::bench::{measure, percent_change, Report};
+use aoc::fetch::{Fetcher, DEFAULT_BASE_URL};
+use aoc::memory::format_bytes;
+use aoc::variants::Variant;
+use aoc::{
+    calendar, day, days, examples, scaffold, serve, variants, view, watch,
+    workspace_root, Day,
+};
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use common::error::SolveError;
+use common::input::{InputError, Source};
+use common::Part;
+use tiny_http::Server;
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOC: aoc::memory::Counting = aoc::memory::Counting;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2023 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a single day, or every day with `all`.
+    Run {
+        /// Day number, or `all`.
+        #[arg(value_parser = parse_selection)]
+        day: Selection,
+        /// Only run this part.
+        #[arg(
+            short,
+            long,
+            value_parser = clap::value_parser!(u8).range(1..=2)
+        )]
+        part: Option<u8>,
+        /// Read the input from this file (`-` for stdin) instead of the
+        /// day's src/input.txt.
+        #[arg(short, long, value_name = "PATH")]
+        input: Option<PathBuf>,
+        /// Solve with this algorithm, for the parts of a day that have
+        /// several (see `aoc diff`).
+        #[arg(long, value_name = "NAME")]
+        variant: Option<String>,
+    },
+    /// Run every algorithm a day has for a part on its examples and report
+    /// any answers they disagree on.
+    Diff {
+        /// Day number, or `all`.
+        #[arg(value_parser = parse_selection, default_value = "all")]
+        day: Selection,
+        /// Compare on this file as well (`-` for stdin).
+        #[arg(short, long, value_name = "PATH")]
+        input: Vec<PathBuf>,
+        /// Compare on the real input as well, which brute force variants
+        /// may take a long time over.
+        #[arg(long)]
+        real: bool,
+    },
+    /// Download puzzle inputs into dayN/src/input.txt.
+    Fetch {
+        /// Day number, or `all` for every solved day.
+        #[arg(value_parser = parse_fetch_selection)]
+        day: Selection,
+        /// Download again even when the input is cached.
+        #[arg(long)]
+        force: bool,
+        #[command(flatten)]
+        remote: Remote,
+    },
+    /// Extract the examples and their answers from a day's puzzle page
+    /// into dayN/examples/.
+    Examples {
+        #[arg(value_parser = parse_selection)]
+        day: Selection,
+        /// Read this saved puzzle page instead of the cached download.
+        #[arg(long, value_name = "PATH")]
+        html: Option<PathBuf>,
+        /// Download the page again, e.g. once part two is unlocked.
+        #[arg(long)]
+        force: bool,
+        #[command(flatten)]
+        remote: Remote,
+    },
+    /// Create dayN from daily-template and register it with the runner.
+    New {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Step through a day's simulation in the terminal (days 10, 14, 16).
+    View {
+        #[arg(value_parser = parse_selection)]
+        day: Selection,
+        /// Read the input from this file (`-` for stdin) instead of the
+        /// day's src/input.txt.
+        #[arg(short, long, value_name = "PATH")]
+        input: Option<PathBuf>,
+        /// Frames per second to start playing at.
+        #[arg(long, default_value_t = 10)]
+        fps: u32,
+    },
+    /// Save a picture of a day's grid as SVG or PNG (days 10, 14, 16).
+    Draw {
+        #[arg(value_parser = parse_selection)]
+        day: Selection,
+        /// Where to write it; the extension picks the format.
+        output: PathBuf,
+        /// Read the input from this file (`-` for stdin) instead of the
+        /// day's src/input.txt.
+        #[arg(short, long, value_name = "PATH")]
+        input: Option<PathBuf>,
+        /// Side of a cell in pixels.
+        #[arg(
+            long,
+            default_value_t = 8,
+            value_parser = clap::value_parser!(u32).range(1..)
+        )]
+        cell: u32,
+    },
+    /// Serve a page showing the states of days 10, 11 and 16 as SVG, with
+    /// the states themselves as JSON under /api/.
+    Serve {
+        /// Address to listen on; keep it local, nothing is authenticated.
+        #[arg(long, default_value = "127.0.0.1:8023")]
+        addr: String,
+    },
+    /// Time every part on its real input and count its allocations,
+    /// optionally against a baseline.
+    Bench {
+        /// Day number, or `all`.
+        #[arg(value_parser = parse_selection, default_value = "all")]
+        day: Selection,
+        /// Seconds to spend timing each part.
+        #[arg(long, default_value_t = 1.0)]
+        time: f64,
+        /// Write the results to this JSON file.
+        #[arg(long, value_name = "PATH")]
+        save: Option<PathBuf>,
+        /// Compare against results saved earlier with `--save`.
+        #[arg(long, value_name = "PATH")]
+        baseline: Option<PathBuf>,
+        /// Percentage a median may grow before it counts as a regression.
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Rebuild a day, run its tests and solve its input again whenever its
+    /// sources, examples or input change, showing how the answers moved.
+    Watch {
+        #[arg(value_parser = parse_selection)]
+        day: Selection,
+        /// Milliseconds between looks for changes.
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+    },
+    /// Solve every part at once on a thread pool and list each one's time,
+    /// peak memory and answer, slowest first.
+    Calendar {
+        /// Seconds a part may take before it is flagged.
+        #[arg(long, default_value_t = 1.0)]
+        budget: f64,
+        /// Threads in the pool, one per core by default.
+        #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+        threads: Option<u16>,
+        /// Also solve with every other algorithm a part has, such as day
+        /// 5's brute force.
+        #[arg(long)]
+        variants: bool,
+    },
+}
+
+#[derive(Args)]
+struct Remote {
+    #[arg(long, default_value_t = 2023)]
+    year: u16,
+    /// Session cookie of a logged in browser.
+    #[arg(long, env = "SESSION", hide_env_values = true)]
+    session: Option<String>,
+    /// Where to send requests, e.g. a local stand-in server.
+    #[arg(long, env = "AOC_BASE_URL", default_value = DEFAULT_BASE_URL)]
+    base_url: String,
+    /// Where downloaded pages and inputs are cached.
+    #[arg(long, value_name = "DIR")]
+    cache_dir: Option<PathBuf>,
+}
+
+impl Remote {
+    fn fetcher(self) -> Fetcher {
+        let cache_dir = self
+            .cache_dir
+            .unwrap_or_else(|| workspace_root().join(".aoc-cache"));
+        Fetcher::new(&self.base_url, self.session, self.year, &cache_dir)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Selection {
+    All,
+    Day(u8),
+}
+
+fn parse_selection(s: &str) -> Result<Selection, String> {
+    if s == "all" {
+        return Ok(Selection::All);
+    }
+    let number = s
+        .trim_start_matches("day")
+        .parse::<u8>()
+        .map_err(|_| format!("`{s}` is neither a day number nor `all`"))?;
+    match day(number) {
+        Some(_) => Ok(Selection::Day(number)),
+        None => Err(format!("day {number} is not solved yet")),
+    }
+}
+
+fn parse_fetch_selection(s: &str) -> Result<Selection, String> {
+    match s.trim_start_matches("day").parse::<u8>() {
+        Ok(number @ 1..=25) => Ok(Selection::Day(number)),
+        _ => parse_selection(s),
+    }
+}
+
+impl Selection {
+    /// The selected days. `single_only` names an option that was given and
+    /// only makes sense for one day, such as `--input`.
+    fn days(self, single_only: Option<&str>) -> Vec<Day> {
+        match (self, single_only) {
+            (Selection::All, Some(option)) => Cli::command()
+                .error(
+                    ErrorKind::ArgumentConflict,
+                    format!("{option} can only be used with a single day"),
+                )
+                .exit(),
+            (Selection::All, None) => days(),
+            (Selection::Day(number), _) => day(number).into_iter().collect(),
+        }
+    }
+
+    /// The selected day, for commands that `action` one day at a time.
+    fn day(self, action: &str) -> Day {
+        let Selection::Day(number) = self else {
+            Cli::command()
+                .error(
+                    ErrorKind::InvalidValue,
+                    format!("pick a single day to {action}"),
+                )
+                .exit()
+        };
+        day(number).expect("selection should be a solved day")
+    }
+}
+
+fn main() -> ExitCode {
+    common::trace::init();
+    match Cli::parse().command {
+        Command::Run {
+            day,
+            part,
+            input,
+            variant,
+        } => run(day, part, input, variant),
+        Command::Diff { day, input, real } => diff(day, &input, real),
+        Command::Fetch { day, force, remote } => fetch(day, force, remote),
+        Command::Examples {
+            day,
+            html,
+            force,
+            remote,
+        } => extract_examples(day, html, force, remote),
+        Command::New { day } => new(day),
+        Command::View { day, input, fps } => view(day, input, fps),
+        Command::Draw {
+            day,
+            output,
+            input,
+            cell,
+        } => draw(day, &output, input, cell),
+        Command::Serve { addr } => serve(&addr),
+        Command::Bench {
+            day,
+            time,
+            save,
+            baseline,
+            threshold,
+        } => bench(day, time, save, baseline, threshold),
+        Command::Watch { day, interval } => watch(day, interval),
+        Command::Calendar {
+            budget,
+            threads,
+            variants,
+        } => calendar(budget, threads, variants),
+    }
+}
+
+fn run(
+    selection: Selection,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    variant: Option<String>,
+) -> ExitCode {
+    let days = selection.days(input.as_ref().map(|_| "--input"));
+    let mut parts = match part {
+        Some(1) => vec![Part::Part1],
+        Some(_) => vec![Part::Part2],
+        None => vec![Part::Part1, Part::Part2],
+    };
+    match (&variant, days.as_slice()) {
+        (None, _) => {}
+        (Some(name), [day]) => {
+            let names = variants::names(day);
+            if !names.contains(&name.as_str()) {
+                Cli::command()
+                    .error(
+                        ErrorKind::InvalidValue,
+                        format!(
+                            "day{} has no variant {name:?}, only {names:?}",
+                            day.number
+                        ),
+                    )
+                    .exit()
+            }
+            // Run only the parts that have it, such as day 5's brute force.
+            if let [part] = parts.as_slice() {
+                if variants::find(day, *part, name).is_none() {
+                    Cli::command()
+                        .error(
+                            ErrorKind::InvalidValue,
+                            format!(
+                                "day{} {part} has no variant {name:?}",
+                                day.number
+                            ),
+                        )
+                        .exit()
+                }
+            }
+            parts.retain(|part| variants::find(day, *part, name).is_some());
+        }
+        (Some(_), _) => Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--variant can only be used with a single day",
+            )
+            .exit(),
+    }
+    let mut failures = 0;
+    for day in days.iter() {
+        match read_input(day, input.as_deref()) {
+            Ok(input) => {
+                failures += parts
+                    .iter()
+                    .filter(|p| {
+                        let variant = variant.as_deref().map(|name| {
+                            variants::find(day, **p, name)
+                                .expect("should only run parts with it")
+                        });
+                        !run_part(day, **p, &input, variant)
+                    })
+                    .count()
+            }
+            Err(err) => {
+                eprintln!("day{:<2}: {err}", day.number);
+                failures += parts.len();
+            }
+        }
+    }
+    if failures > 0 {
+        eprintln!("{failures} part(s) failed");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn diff(selection: Selection, input: &[PathBuf], real: bool) -> ExitCode {
+    let days = selection.days((!input.is_empty()).then_some("--input"));
+    let mut disagreements = 0;
+    for day in days.iter().filter(|d| !variants::names(d).is_empty()) {
+        let mut paths = examples::files(day);
+        if real {
+            paths.push(day.input_path());
+        }
+        paths.extend(input.iter().cloned());
+        for path in paths.iter() {
+            let text = match read_input(day, Some(path)) {
+                Ok(text) => text,
+                Err(err) => {
+                    eprintln!("day{:<2}: {err}", day.number);
+                    disagreements += 1;
+                    continue;
+                }
+            };
+            for part in [Part::Part1, Part::Part2] {
+                let Some(comparison) = variants::compare(day, part, &text)
+                else {
+                    continue;
+                };
+                let label = format!(
+                    "day{:<2} {} on {}",
+                    day.number,
+                    part,
+                    display_path(path)
+                );
+                if comparison.agrees() {
+                    println!("{label}: {comparison}");
+                } else {
+                    eprintln!("{label}: disagree, {comparison}");
+                    eprintln!("{}", excerpt(&text));
+                    disagreements += 1;
+                }
+            }
+        }
+    }
+    if disagreements > 0 {
+        eprintln!("{disagreements} disagreement(s)");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn fetch(selection: Selection, force: bool, remote: Remote) -> ExitCode {
+    let fetcher = remote.fetcher();
+    // Unlike the other commands, fetch takes days that are not solved yet.
+    let numbers: Vec<u8> = match selection {
+        Selection::All => days().iter().map(|d| d.number).collect(),
+        Selection::Day(number) => vec![number],
+    };
+    let mut failures = 0;
+    for number in numbers {
+        let src = workspace_root().join(format!("day{number}/src"));
+        if !src.is_dir() {
+            eprintln!("day{number}: {src:?} is missing, create the day first");
+            failures += 1;
+            continue;
+        }
+        match fetcher.install(number, &src.join("input.txt"), force) {
+            Ok(()) => println!("day{number}: input ready"),
+            Err(err) => {
+                eprintln!("day{number}: {err}");
+                failures += 1;
+            }
+        }
+    }
+    if failures > 0 {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn extract_examples(
+    selection: Selection,
+    html: Option<PathBuf>,
+    force: bool,
+    remote: Remote,
+) -> ExitCode {
+    let days = selection.days(html.as_ref().map(|_| "--html"));
+    let fetcher = remote.fetcher();
+    let mut failures = 0;
+    for day in days.iter() {
+        let page = match &html {
+            Some(path) => fs::read_to_string(path)
+                .map_err(|e| format!("cannot read {path:?}: {e}")),
+            None => {
+                fetcher.puzzle(day.number, force).map_err(|e| e.to_string())
+            }
+        };
+        let extracted = page.map(|page| examples::extract(&page));
+        let written = extracted.and_then(|extracted| {
+            examples::write(day, &extracted)
+                .map(|_| extracted)
+                .map_err(|e| e.to_string())
+        });
+        match written {
+            Ok(extracted) => println!(
+                "day{}: {} example(s), answers {:?} / {:?}",
+                day.number,
+                extracted.examples.len(),
+                extracted.part1.map(|(_, a)| a),
+                extracted.part2.map(|(_, a)| a),
+            ),
+            Err(err) => {
+                eprintln!("day{}: {err}", day.number);
+                failures += 1;
+            }
+        }
+    }
+    if failures > 0 {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn new(day: u8) -> ExitCode {
+    if let Err(err) = scaffold::create(workspace_root(), day) {
+        eprintln!("day{day}: {err}");
+        return ExitCode::FAILURE;
+    }
+    println!("day{day}: created, fetch its input next");
+    ExitCode::SUCCESS
+}
+
+fn view(selection: Selection, input: Option<PathBuf>, fps: u32) -> ExitCode {
+    let day = selection.day("view");
+    let input = match read_input(&day, input.as_deref()) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("day{:<2}: {err}", day.number);
+            return ExitCode::FAILURE;
+        }
+    };
+    let replay = match view::record(&day, &input) {
+        Some(Ok(replay)) => replay,
+        Some(Err(err)) => {
+            eprintln!("day{:<2}: malformed input\n{err}", day.number);
+            return ExitCode::FAILURE;
+        }
+        None => {
+            eprintln!("day{}: has no simulation to view", day.number);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(err) = view::play(&day, replay.as_ref(), fps) {
+        eprintln!("day{}: {err}", day.number);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn draw(
+    selection: Selection,
+    output: &Path,
+    input: Option<PathBuf>,
+    cell: u32,
+) -> ExitCode {
+    let day = selection.day("draw");
+    let input = match read_input(&day, input.as_deref()) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("day{:<2}: {err}", day.number);
+            return ExitCode::FAILURE;
+        }
+    };
+    let picture = match view::picture(&day, &input) {
+        Some(Ok(picture)) => picture,
+        Some(Err(err)) => {
+            eprintln!("day{:<2}: malformed input\n{err}", day.number);
+            return ExitCode::FAILURE;
+        }
+        None => {
+            eprintln!("day{}: has no grid to draw", day.number);
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Err(err) = picture.save(output, cell) {
+        eprintln!("cannot write {output:?}: {err}");
+        return ExitCode::FAILURE;
+    }
+    println!(
+        "day{}: {}, saved to {}",
+        day.number,
+        picture.title(),
+        display_path(output)
+    );
+    ExitCode::SUCCESS
+}
+
+fn serve(addr: &str) -> ExitCode {
+    let server = match Server::http(addr) {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("cannot listen on {addr}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("serving on http://{}", server.server_addr());
+    serve::serve(server);
+    ExitCode::SUCCESS
+}
+
+fn bench(
+    selection: Selection,
+    time: f64,
+    save: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+) -> ExitCode {
+    let baseline = match baseline.as_deref().map(Report::load) {
+        Some(Ok(report)) => Some(report),
+        Some(Err(err)) => {
+            eprintln!("cannot read baseline: {err}");
+            return ExitCode::FAILURE;
+        }
+        None => None,
+    };
+    let budget = Duration::from_secs_f64(time);
+    let mut report = Report::default();
+    for day in selection.days(None).iter() {
+        let input = match read_input(day, None) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("day{:<2}: {err}", day.number);
+                return ExitCode::FAILURE;
+            }
+        };
+        for part in [Part::Part1, Part::Part2] {
+            let sample = measure(day, part, &input, budget);
+            let before = baseline
+                .as_ref()
+                .and_then(|b| b.get(sample.day, sample.part));
+            let change = before
+                .map(|b| {
+                    let change = percent_change(b.median_ns, sample.median_ns);
+                    format!(" {change:+.1}%")
+                })
+                .unwrap_or_default();
+            let allocations =
+                match (sample.allocations, sample.allocated_bytes) {
+                    (Some(n), Some(bytes)) => {
+                        let change = before
+                            .and_then(|b| b.allocations)
+                            .filter(|&b| b != n)
+                            .map(|b| {
+                                let change = percent_change(b, n);
+                                format!(" {change:+.1}%")
+                            })
+                            .unwrap_or_default();
+                        format!(
+                            ", {n} allocs{change} ({})",
+                            format_bytes(bytes as usize)
+                        )
+                    }
+                    _ => String::new(),
+                };
+            println!(
+                "{}: {:>10.2?} median, {:>10.2?} min, {} runs{}{}",
+                sample.label(),
+                Duration::from_nanos(sample.median_ns),
+                Duration::from_nanos(sample.min_ns),
+                sample.iterations,
+                change,
+                allocations
+            );
+            report.samples.push(sample);
+        }
+    }
+    if let Some(path) = save {
+        if let Err(err) = report.save(&path) {
+            eprintln!("cannot save {path:?}: {err}");
+            return ExitCode::FAILURE;
+        }
+    }
+    let regressions = baseline
+        .map(|b| report.regressions(&b, threshold))
+        .unwrap_or_default();
+    if !regressions.is_empty() {
+        eprintln!("regressions above {threshold}%:");
+        for regression in regressions {
+            eprintln!("  {regression}");
+        }
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn watch(selection: Selection, interval: u64) -> ExitCode {
+    let day = selection.day("watch");
+    let paths = watch::watched(&day);
+    let interval = Duration::from_millis(interval);
+    let mut stamps = watch::Stamps::scan(&paths);
+    let mut previous: Option<Vec<String>> = None;
+    loop {
+        match watch::check(&day) {
+            Ok(answers) => {
+                let report =
+                    watch::report(&day, previous.as_deref(), &answers);
+                for line in report {
+                    println!("day{:<2} {line}", day.number);
+                }
+                previous = Some(answers);
+            }
+            Err(err) => eprintln!("day{:<2}: {err}", day.number),
+        }
+        println!("day{:<2}: watching for changes", day.number);
+        // Wait for a change, then for the files to settle so a save
+        // touching several of them runs once.
+        let changed = loop {
+            thread::sleep(interval);
+            let now = watch::Stamps::scan(&paths);
+            let changed = now.changed(&stamps);
+            if !changed.is_empty() {
+                stamps = now;
+                break changed;
+            }
+        };
+        loop {
+            thread::sleep(interval);
+            let now = watch::Stamps::scan(&paths);
+            if now == stamps {
+                break;
+            }
+            stamps = now;
+        }
+        for path in changed {
+            println!("changed {}", display_path(&path));
+        }
+    }
+}
+
+fn calendar(budget: f64, threads: Option<u16>, variants: bool) -> ExitCode {
+    let mut failures = 0;
+    let mut inputs = vec![];
+    for day in days() {
+        match read_input(&day, None) {
+            Ok(input) => inputs.push((day, input)),
+            Err(err) => {
+                eprintln!("day{:<2}: {err}", day.number);
+                failures += 2;
+            }
+        }
+    }
+    let jobs = calendar::jobs(&inputs, variants);
+    let calendar = match calendar::run(&jobs, threads.map(usize::from)) {
+        Ok(calendar) => calendar,
+        Err(err) => {
+            eprintln!("cannot start the thread pool: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let budget = Duration::from_secs_f64(budget);
+    let color = std::io::stdout().is_terminal();
+    println!("{}", calendar.table(budget, color));
+    failures += calendar.timings.iter().filter(|t| t.failed()).count();
+    if failures > 0 {
+        eprintln!("{failures} part(s) failed");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn read_input(day: &Day, path: Option<&Path>) -> Result<String, InputError> {
+    let source = match path {
+        Some(path) if path == Path::new("-") => Source::Stdin,
+        Some(path) => Source::File(path.to_path_buf()),
+        None => Source::File(day.input_path()),
+    };
+    source.read()
+}
+
+/// A path as given, or relative to the workspace when it is inside it.
+fn display_path(path: &Path) -> String {
+    if path == Path::new("-") {
+        return "stdin".to_string();
+    }
+    path.strip_prefix(workspace_root())
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// The input that made variants disagree, indented, or its first lines
+/// when it is long.
+fn excerpt(input: &str) -> String {
+    const LINES: usize = 20;
+    let lines: Vec<&str> = input.lines().collect();
+    let mut excerpt: Vec<String> = lines
+        .iter()
+        .take(LINES)
+        .map(|l| format!("    {l}"))
+        .collect();
+    if lines.len() > LINES {
+        excerpt.push(format!("    ... {} more lines", lines.len() - LINES));
+    }
+    excerpt.join("\n")
+}
+
+/// Runs one part of a day, printing the answer and its wall-clock time,
+/// with `variant` instead of the day's own algorithm when given.
+/// Returns false when the input is malformed, has no answer or the solver
+/// panics.
+fn run_part(
+    day: &Day,
+    part: Part,
+    input: &str,
+    variant: Option<Variant>,
+) -> bool {
+    let label = match variant {
+        Some(variant) => {
+            format!("day{:<2} {} [{}]", day.number, part, variant.name)
+        }
+        None => format!("day{:<2} {}", day.number, part),
+    };
+    let start = Instant::now();
+    let answer = catch_unwind(|| match variant {
+        Some(variant) => variant.solve(input),
+        None => day.solve(input, part),
+    });
+    let elapsed = start.elapsed();
+    match answer {
+        Ok(Ok(answer)) => {
+            println!("{label}: {answer:<20} ({elapsed:.2?})");
+            true
+        }
+        Ok(Err(SolveError::Malformed(err))) => {
+            eprintln!("{label}: malformed input\n{err}");
+            false
+        }
+        Ok(Err(SolveError::NoAnswer(message))) => {
+            eprintln!("{label}: no answer, {message}");
+            false
+        }
+        Err(_) => {
+            eprintln!("{label}: panicked after {elapsed:.2?}");
+            false
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! A global allocator that counts each thread's allocations and the bytes it
+//! holds, so the runner can tell how much memory a part peaked at and how
+//! much heap churn it caused. Binaries opt in with
+//! `#[global_allocator] static ALLOC: Counting = Counting;`, which the `aoc`
+//! binary does behind its `count-allocations` feature.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The system allocator, keeping per thread counts on the side.
+pub struct Counting;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Bytes this thread allocated minus those it freed, which goes below
+    /// zero when it frees what another thread allocated.
+    static CURRENT: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+    /// Allocations and reallocations this thread made.
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    /// Bytes this thread asked for, freed or not.
+    static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts `requested` more bytes handed out, and `delta` more bytes held.
+fn record(requested: usize, delta: isize) {
+    INSTALLED.store(true, Ordering::Relaxed);
+    // A thread's locals are gone while it shuts down; stop counting then.
+    let _ = CURRENT.try_with(|current| {
+        let now = current.get() + delta;
+        current.set(now);
+        let _ = PEAK.try_with(|peak| peak.set(peak.get().max(now)));
+    });
+    if requested > 0 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        let _ = ALLOCATED.try_with(|bytes| bytes.set(bytes.get() + requested));
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), layout.size() as isize);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, -(layout.size() as isize));
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            record(new_size, new_size as isize - layout.size() as isize);
+        }
+        new
+    }
+}
+
+/// What the current thread allocated while running a closure.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub allocations: usize,
+    /// Bytes asked for, freed or not.
+    pub allocated: usize,
+    /// The most bytes held at once, on top of what was held before.
+    pub peak: usize,
+}
+
+/// Runs `f` and returns what the current thread allocated meanwhile, or
+/// `None` when `Counting` is not the global allocator.
+pub fn usage<T>(f: impl FnOnce() -> T) -> (T, Option<Usage>) {
+    let start = CURRENT.with(Cell::get);
+    PEAK.with(|peak| peak.set(start));
+    let allocations = ALLOCATIONS.with(Cell::get);
+    let allocated = ALLOCATED.with(Cell::get);
+    let out = f();
+    let usage = Usage {
+        allocations: ALLOCATIONS.with(Cell::get) - allocations,
+        allocated: ALLOCATED.with(Cell::get) - allocated,
+        peak: (PEAK.with(Cell::get) - start).max(0) as usize,
+    };
+    (out, INSTALLED.load(Ordering::Relaxed).then_some(usage))
+}
+
+/// Bytes in binary units, such as `1.5 MiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOC: Counting = Counting;
+
+    #[test]
+    fn test_usage() {
+        let (sum, usage) = usage(|| {
+            let big = vec![1u8; 1 << 20];
+            let mut small = Vec::with_capacity(1 << 10);
+            small.extend([1u8; 1 << 10]);
+            drop(big);
+            small.extend([1u8; 1 << 10]);
+            small.len()
+        });
+        assert_eq!(sum, 1 << 11);
+        assert_eq!(
+            usage,
+            Some(Usage {
+                allocations: 3,
+                allocated: (1 << 20) + (1 << 10) + (1 << 11),
+                peak: (1 << 20) + (1 << 10),
+            })
+        );
+        let (_, usage) = super::usage(|| 1 + 1);
+        assert_eq!(usage, Some(Usage::default()));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 << 20), "3.0 MiB");
+    }
+}
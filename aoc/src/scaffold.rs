@@ -0,0 +1,182 @@
+use std::path::Path;
+use std::{fs, io};
+
+/// Renders `daily-template` into `root/dayN` and wires the new crate into
+/// the runner, so `aoc run N` and `cargo bench -p aoc` pick it up.
+pub fn create(root: &Path, day: u8) -> io::Result<()> {
+    let dest = root.join(format!("day{}", day));
+    if dest.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", dest.display()),
+        ));
+    }
+    render_dir(&root.join("daily-template"), &dest, day)?;
+    register(root, day)
+}
+
+fn render_dir(template: &Path, dest: &Path, day: u8) -> io::Result<()> {
+    fs::create_dir_all(dest)?;
+    for entry in fs::read_dir(template)? {
+        let entry = entry?;
+        let target = dest.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            render_dir(&entry.path(), &target, day)?;
+        } else {
+            let contents = fs::read_to_string(entry.path())?;
+            fs::write(target, render(&contents, day))?;
+        }
+    }
+    Ok(())
+}
+
+/// Fills in the `{{project-name}}` and `{{day}}` placeholders.
+pub fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{{project-name}}", &format!("day{}", day))
+        .replace("{{day}}", &day.to_string())
+}
+
+/// Adds the day to the runner's dependencies and registry, and gives it a
+/// fuzz target. Days already registered are left alone.
+pub fn register(root: &Path, day: u8) -> io::Result<()> {
+    edit(&root.join("aoc/Cargo.toml"), |s| add_dependency(s, day))?;
+    edit(&root.join("aoc/src/lib.rs"), |s| add_to_registry(s, day))?;
+    let fuzz = root.join("fuzz");
+    edit(&fuzz.join("Cargo.toml"), |s| {
+        add_dependency(s, day).map(|s| add_fuzz_target(&s, day))
+    })?;
+    fs::write(
+        fuzz.join(format!("fuzz_targets/day{}.rs", day)),
+        render(FUZZ_TARGET, day),
+    )
+}
+
+const FUZZ_TARGET: &str = "\
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| aoc_fuzz::solve::<day{{day}}::Day{{day}}>(input));
+";
+
+pub fn add_fuzz_target(manifest: &str, day: u8) -> String {
+    let name = format!("name = \"day{}\"", day);
+    if manifest.lines().any(|l| l == name) {
+        return manifest.to_string();
+    }
+    format!(
+        "{}\n[[bin]]\n{}\npath = \"fuzz_targets/day{}.rs\"\n\
+         test = false\ndoc = false\nbench = false\n",
+        manifest, name, day
+    )
+}
+
+fn edit(path: &Path, f: impl Fn(&str) -> Option<String>) -> io::Result<()> {
+    let contents = fs::read_to_string(path)?;
+    let edited = f(&contents).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("cannot find where to register in {}", path.display()),
+        )
+    })?;
+    if edited != contents {
+        fs::write(path, edited)?;
+    }
+    Ok(())
+}
+
+/// Inserts `line` after the last line starting with `prefix`, unless a line
+/// equal to `line` is already there.
+fn insert_after_last(
+    contents: &str,
+    prefix: &str,
+    line: &str,
+) -> Option<String> {
+    let mut lines: Vec<&str> = contents.lines().collect();
+    if lines.iter().any(|l| l.trim() == line.trim()) {
+        return Some(contents.to_string());
+    }
+    let last = lines.iter().rposition(|l| l.starts_with(prefix))?;
+    lines.insert(last + 1, line);
+    Some(lines.join("\n") + "\n")
+}
+
+pub fn add_dependency(manifest: &str, day: u8) -> Option<String> {
+    let line = format!("day{0} = {{ path = \"../day{0}\" }}", day);
+    insert_after_last(manifest, "day", &line)
+}
+
+pub fn add_to_registry(lib: &str, day: u8) -> Option<String> {
+    let line = format!("        Day::of::<day{0}::Day{0}>(),", day);
+    insert_after_last(lib, "        Day::of::<", &line)
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            render("use {{project-name}}::Day{{day}};", 17),
+            "use day17::Day17;"
+        );
+    }
+
+    #[test]
+    fn test_add_dependency() {
+        let manifest = indoc! {r#"
+            [dependencies]
+            day1 = { path = "../day1" }
+            day2 = { path = "../day2" }
+
+            [dev-dependencies]
+            "#};
+        let expected = indoc! {r#"
+            [dependencies]
+            day1 = { path = "../day1" }
+            day2 = { path = "../day2" }
+            day3 = { path = "../day3" }
+
+            [dev-dependencies]
+            "#};
+        assert_eq!(add_dependency(manifest, 3).unwrap(), expected);
+        assert_eq!(add_dependency(expected, 3).unwrap(), expected);
+        assert_eq!(add_dependency("[dependencies]\n", 3), None);
+    }
+
+    #[test]
+    fn test_add_fuzz_target() {
+        let manifest = "[dependencies]\n";
+        let expected = indoc! {r#"
+            [dependencies]
+
+            [[bin]]
+            name = "day3"
+            path = "fuzz_targets/day3.rs"
+            test = false
+            doc = false
+            bench = false
+            "#};
+        assert_eq!(add_fuzz_target(manifest, 3), expected);
+        assert_eq!(add_fuzz_target(expected, 3), expected);
+        let target = crate::workspace_root().join("fuzz/fuzz_targets/day3.rs");
+        assert_eq!(
+            render(FUZZ_TARGET, 3),
+            fs::read_to_string(target).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_add_to_registry() {
+        let lib = "    vec![\n        Day::of::<day1::Day1>(),\n    ]\n";
+        assert_eq!(
+            add_to_registry(lib, 2).unwrap(),
+            "    vec![\n        Day::of::<day1::Day1>(),\n        \
+             Day::of::<day2::Day2>(),\n    ]\n"
+        );
+    }
+}
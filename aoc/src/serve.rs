@@ -0,0 +1,208 @@
+use std::fs;
+use std::path::PathBuf;
+
+use common::error::ParseError;
+use common::input::normalize;
+use common::snapshot::Snapshot;
+use serde_json::{json, Value};
+use tiny_http::{Header, Response, Server};
+
+use crate::examples::{self, examples_dir};
+use crate::{day, days, Day};
+
+/// The page drawing the states as SVG, served at `/`.
+pub const PAGE: &str = include_str!("../static/index.html");
+
+type Snapshots = fn(&str) -> Result<Vec<Snapshot>, ParseError>;
+
+/// The solver code recording the day's states, for the days that have
+/// some to show.
+fn snapshots_of(day: &Day) -> Option<Snapshots> {
+    match day.number {
+        10 => Some(day10::snapshots),
+        11 => Some(day11::snapshots),
+        16 => Some(day16::snapshots),
+        _ => None,
+    }
+}
+
+/// The inputs a day can be shown on: `input`, its real input, when it has
+/// been fetched, and its `exampleN`s.
+pub fn inputs(day: &Day) -> Vec<String> {
+    let mut inputs = vec![];
+    if day.input_path().exists() {
+        inputs.push("input".to_string());
+    }
+    inputs.extend(
+        examples::files(day)
+            .iter()
+            .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string())),
+    );
+    inputs
+}
+
+/// Where the input called `name` lives, refusing anything but `input` and
+/// `exampleN` so requests cannot reach other files.
+fn input_path(day: &Day, name: &str) -> Option<PathBuf> {
+    match name.strip_prefix("example") {
+        _ if name == "input" => Some(day.input_path()),
+        Some(n) if !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()) => {
+            Some(examples_dir(day).join(format!("{}.txt", name)))
+        }
+        _ => None,
+    }
+}
+
+pub fn to_json(snapshot: &Snapshot) -> Value {
+    let layers: Vec<Value> = snapshot
+        .layers
+        .iter()
+        .map(|layer| json!({"name": layer.name, "cells": layer.cells}))
+        .collect();
+    json!({
+        "caption": snapshot.caption,
+        "rows": snapshot.rows,
+        "layers": layers,
+    })
+}
+
+/// What the server answers a request with.
+#[derive(Debug, PartialEq)]
+pub struct Reply {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Reply {
+    fn json(status: u16, body: Value) -> Reply {
+        Reply {
+            status,
+            content_type: "application/json",
+            body: body.to_string(),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Reply {
+        Reply::json(status, json!({"error": message.into()}))
+    }
+}
+
+/// Answers `GET url`: the page at `/`, the days with states to show at
+/// `/api/days` and a day's states at `/api/day/N/INPUT`.
+pub fn respond(url: &str) -> Reply {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let segments: Vec<&str> =
+        path.split('/').filter(|s| !s.is_empty()).collect();
+    match segments.as_slice() {
+        [] | ["index.html"] => Reply {
+            status: 200,
+            content_type: "text/html; charset=utf-8",
+            body: PAGE.to_string(),
+        },
+        ["api", "days"] => {
+            let days: Vec<Value> = days()
+                .iter()
+                .filter(|day| snapshots_of(day).is_some())
+                .map(|day| json!({"day": day.number, "inputs": inputs(day)}))
+                .collect();
+            Reply::json(200, Value::from(days))
+        }
+        ["api", "day", number, name] => states(number, name),
+        _ => Reply::error(404, format!("nothing at {}", path)),
+    }
+}
+
+fn states(number: &str, name: &str) -> Reply {
+    let Some(day) = number.parse().ok().and_then(day) else {
+        return Reply::error(404, format!("day {} is not solved", number));
+    };
+    let Some(snapshots) = snapshots_of(&day) else {
+        return Reply::error(404, format!("day{} has no states", day.number));
+    };
+    let Some(path) = input_path(&day, name) else {
+        return Reply::error(404, format!("no input called {}", name));
+    };
+    let input = match fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(err) => {
+            return Reply::error(
+                404,
+                format!("cannot read {:?}: {}", path, err),
+            )
+        }
+    };
+    match snapshots(&normalize(&input)) {
+        Ok(snapshots) => Reply::json(
+            200,
+            json!({
+                "day": day.number,
+                "input": name,
+                "states": snapshots.iter().map(to_json).collect::<Vec<_>>(),
+            }),
+        ),
+        Err(err) => Reply::error(422, format!("malformed input\n{}", err)),
+    }
+}
+
+/// Answers requests on `server` until the process is stopped.
+pub fn serve(server: Server) {
+    for request in server.incoming_requests() {
+        let reply = respond(request.url());
+        let content_type =
+            Header::from_bytes("Content-Type", reply.content_type)
+                .expect("content type should be a valid header");
+        let response = Response::from_string(reply.body)
+            .with_status_code(reply.status)
+            .with_header(content_type);
+        if let Err(err) = request.respond(response) {
+            eprintln!("cannot reply: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_respond() {
+        assert_eq!(respond("/").content_type, "text/html; charset=utf-8");
+        let days: Value = serde_json::from_str(&respond("/api/days").body)
+            .expect("should be json");
+        let numbers: Vec<u64> = days
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| d["day"].as_u64().unwrap())
+            .collect();
+        assert_eq!(numbers, [10, 11, 16]);
+        assert_eq!(days[0]["inputs"][0], "input");
+
+        let reply = respond("/api/day/11/example1?t=1");
+        assert_eq!(reply.status, 200);
+        let states: Value = serde_json::from_str(&reply.body).unwrap();
+        assert_eq!(states["states"][1]["caption"], "expanded universe");
+        assert_eq!(
+            states["states"][1]["layers"][0]["cells"][0],
+            json!([0, 4])
+        );
+
+        assert_eq!(respond("/api/day/11/..%2Finput").status, 404);
+        assert_eq!(respond("/api/day/11/example").status, 404);
+        assert_eq!(respond("/api/day/5/input").status, 404);
+        assert_eq!(respond("/api/day/99/input").status, 404);
+        assert_eq!(respond("/favicon.ico").status, 404);
+    }
+
+    #[test]
+    fn test_input_path() {
+        let day = day(10).unwrap();
+        assert_eq!(input_path(&day, "input"), Some(day.input_path()));
+        assert!(input_path(&day, "example2")
+            .unwrap()
+            .ends_with("day10/examples/example2.txt"));
+        assert_eq!(input_path(&day, "example../x"), None);
+        assert_eq!(input_path(&day, "answers"), None);
+    }
+}
@@ -0,0 +1,244 @@
+use std::fmt::{Display, Formatter};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use common::error::SolveError;
+use common::input::normalize;
+use common::{Answer, Part, Solution};
+
+use crate::Day;
+
+/// One of the algorithms a day carries for a part. Each solves the same
+/// normalized input and checks it the way the day's `Solution` does.
+#[derive(Clone, Copy)]
+pub struct Variant {
+    pub day: u8,
+    pub part: Part,
+    pub name: &'static str,
+    solve: fn(&str) -> Result<Answer, SolveError>,
+}
+
+impl Variant {
+    pub fn solve(&self, input: &str) -> Result<Answer, SolveError> {
+        (self.solve)(&normalize(input))
+    }
+}
+
+/// Every registered variant. The first one for a part is the algorithm the
+/// day's `Solution` uses.
+pub fn variants() -> Vec<Variant> {
+    use Part::{Part1, Part2};
+    vec![
+        Variant {
+            day: 3,
+            part: Part1,
+            name: "char-scan",
+            solve: |input| {
+                Ok(day3::gear_sum(day3::Day3::parse(input)?).0.into())
+            },
+        },
+        Variant {
+            day: 3,
+            part: Part1,
+            name: "regex",
+            solve: |input| {
+                Ok(day3::gear_sum_v2(day3::Day3::parse(input)?).0.into())
+            },
+        },
+        Variant {
+            day: 3,
+            part: Part2,
+            name: "char-scan",
+            solve: |input| {
+                Ok(day3::gear_sum(day3::Day3::parse(input)?).1.into())
+            },
+        },
+        Variant {
+            day: 3,
+            part: Part2,
+            name: "regex",
+            solve: |input| {
+                Ok(day3::gear_sum_v2(day3::Day3::parse(input)?).1.into())
+            },
+        },
+        Variant {
+            day: 5,
+            part: Part2,
+            name: "ranges",
+            solve: |input| day5::part2(input).map(Answer::from),
+        },
+        Variant {
+            day: 5,
+            part: Part2,
+            name: "brute-force",
+            solve: |input| day5::part2_brute_force(input).map(Answer::from),
+        },
+        Variant {
+            day: 11,
+            part: Part1,
+            name: "manhattan",
+            solve: |input| {
+                Ok(day11::process_manhattan(day11::parse(input)?, 2).into())
+            },
+        },
+        Variant {
+            day: 11,
+            part: Part1,
+            name: "astar",
+            solve: |input| Ok(day11::process(day11::parse(input)?, 2).into()),
+        },
+        Variant {
+            day: 11,
+            part: Part2,
+            name: "manhattan",
+            solve: |input| {
+                let image = day11::parse(input)?;
+                Ok(day11::process_manhattan(image, 1000000).into())
+            },
+        },
+        Variant {
+            day: 11,
+            part: Part2,
+            name: "astar",
+            solve: |input| {
+                Ok(day11::process(day11::parse(input)?, 1000000).into())
+            },
+        },
+    ]
+}
+
+/// The variants of one part of `day`, empty when it has a single one.
+pub fn of(day: &Day, part: Part) -> Vec<Variant> {
+    variants()
+        .into_iter()
+        .filter(|v| v.day == day.number && v.part == part)
+        .collect()
+}
+
+/// The variant called `name` for one part of `day`.
+pub fn find(day: &Day, part: Part, name: &str) -> Option<Variant> {
+    of(day, part).into_iter().find(|v| v.name == name)
+}
+
+/// The names of `day`'s variants, over both parts.
+pub fn names(day: &Day) -> Vec<&'static str> {
+    let mut names = vec![];
+    for variant in of(day, Part::Part1).iter().chain(&of(day, Part::Part2)) {
+        if !names.contains(&variant.name) {
+            names.push(variant.name);
+        }
+    }
+    names
+}
+
+/// What every variant of a part answered on the same input.
+#[derive(Debug, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: Part,
+    pub answers: Vec<(&'static str, Result<Answer, String>)>,
+}
+
+impl Comparison {
+    pub fn agrees(&self) -> bool {
+        self.answers.windows(2).all(|pair| pair[0].1 == pair[1].1)
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, (name, answer)) in self.answers.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match answer {
+                Ok(answer) => write!(f, "{} = {}", name, answer)?,
+                Err(err) => write!(f, "{} {}", name, err)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs every variant of one part of `day` on `input`, or `None` when the
+/// part has nothing to compare.
+pub fn compare(day: &Day, part: Part, input: &str) -> Option<Comparison> {
+    let variants = of(day, part);
+    if variants.len() < 2 {
+        return None;
+    }
+    let answers = variants
+        .iter()
+        .map(|variant| {
+            let answer =
+                catch_unwind(AssertUnwindSafe(|| variant.solve(input)));
+            let answer = match answer {
+                Ok(Ok(answer)) => Ok(answer),
+                Ok(Err(SolveError::Malformed(err))) => {
+                    Err(format!("failed: {}", err.message()))
+                }
+                Ok(Err(SolveError::NoAnswer(message))) => {
+                    Err(format!("no answer: {}", message))
+                }
+                Err(_) => Err("panicked".to_string()),
+            };
+            (variant.name, answer)
+        })
+        .collect();
+    Some(Comparison {
+        day: day.number,
+        part,
+        answers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day;
+
+    #[test]
+    fn test_variants() {
+        for variant in variants() {
+            let day = day(variant.day).unwrap();
+            let same_name = of(&day, variant.part)
+                .iter()
+                .filter(|v| v.name == variant.name)
+                .count();
+            assert_eq!(same_name, 1, "{} {}", variant.day, variant.name);
+        }
+        assert_eq!(names(&day(11).unwrap()), ["manhattan", "astar"]);
+        assert!(names(&day(1).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_compare() {
+        let day = day(11).unwrap();
+        let input = "#.\n..\n.#\n";
+        let comparison = compare(&day, Part::Part2, input).unwrap();
+        assert!(comparison.agrees());
+        assert_eq!(
+            comparison.to_string(),
+            "manhattan = 1000002, astar = 1000002"
+        );
+        let comparison = compare(&day, Part::Part1, "#x\n").unwrap();
+        assert!(comparison.agrees());
+        assert!(compare(&day, Part::Part1, "").unwrap().answers[0]
+            .1
+            .is_err());
+        assert!(compare(&crate::day(5).unwrap(), Part::Part1, "").is_none());
+    }
+
+    #[test]
+    fn test_disagreement() {
+        let comparison = Comparison {
+            day: 3,
+            part: Part::Part1,
+            answers: vec![
+                ("char-scan", Ok(Answer::from(1u32))),
+                ("regex", Err("panicked".to_string())),
+            ],
+        };
+        assert!(!comparison.agrees());
+        assert_eq!(comparison.to_string(), "char-scan = 1, regex panicked");
+    }
+}
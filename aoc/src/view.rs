@@ -0,0 +1,269 @@
+use std::io;
+use std::time::{Duration, Instant};
+
+use common::error::ParseError;
+use common::input::normalize;
+use common::replay::Replay;
+use picture::Picture;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::DefaultTerminal;
+
+use crate::Day;
+
+/// Records the day's simulation on `input` with its own solver code, for
+/// the days that have one to show.
+pub fn record(
+    day: &Day,
+    input: &str,
+) -> Option<Result<Box<dyn Replay>, ParseError>> {
+    let input = normalize(input);
+    Some(match day.number {
+        10 => day10::replay(&input).map(|r| Box::new(r) as Box<dyn Replay>),
+        14 => day14::replay(&input).map(|r| Box::new(r) as Box<dyn Replay>),
+        16 => day16::replay(&input).map(|r| Box::new(r) as Box<dyn Replay>),
+        _ => return None,
+    })
+}
+
+/// Draws the day's grid on `input` with its own solver code, for the days
+/// that have one to draw: day 14's platform after part two's spin cycles.
+pub fn picture(
+    day: &Day,
+    input: &str,
+) -> Option<Result<Box<dyn Picture>, ParseError>> {
+    let input = normalize(input);
+    Some(match day.number {
+        10 => day10::picture(&input).map(|p| Box::new(p) as Box<dyn Picture>),
+        14 => day14::picture(&input, day14::SPINS)
+            .map(|p| Box::new(p) as Box<dyn Picture>),
+        16 => day16::replay(&input).map(|p| Box::new(p) as Box<dyn Picture>),
+        _ => return None,
+    })
+}
+
+/// What a key press asks the viewer to do.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Control {
+    Pause,
+    Step,
+    Back,
+    Rewind,
+    End,
+    Faster,
+    Slower,
+    Scroll(isize, isize),
+    Quit,
+}
+
+impl Control {
+    pub fn of(key: KeyCode) -> Option<Control> {
+        Some(match key {
+            KeyCode::Char(' ') => Control::Pause,
+            KeyCode::Right | KeyCode::Char('n') => Control::Step,
+            KeyCode::Left | KeyCode::Char('p') => Control::Back,
+            KeyCode::Home | KeyCode::Char('r') => Control::Rewind,
+            KeyCode::End => Control::End,
+            KeyCode::Char('+') | KeyCode::Char('=') => Control::Faster,
+            KeyCode::Char('-') => Control::Slower,
+            KeyCode::Up | KeyCode::Char('k') => Control::Scroll(-1, 0),
+            KeyCode::Down | KeyCode::Char('j') => Control::Scroll(1, 0),
+            KeyCode::PageUp => Control::Scroll(-20, 0),
+            KeyCode::PageDown => Control::Scroll(20, 0),
+            KeyCode::Char('h') => Control::Scroll(0, -4),
+            KeyCode::Char('l') => Control::Scroll(0, 4),
+            KeyCode::Char('q') | KeyCode::Esc => Control::Quit,
+            _ => return None,
+        })
+    }
+}
+
+pub const HELP: &str = "space pause  ←/→ step  r rewind  end last  \
+                        +/- speed  ↑↓hjkl scroll  q quit";
+
+/// Where playback is: the frame shown, whether it moves on by itself and
+/// how fast.
+#[derive(Debug, PartialEq)]
+pub struct Player {
+    pub frame: usize,
+    pub frames: usize,
+    pub paused: bool,
+    pub fps: u32,
+}
+
+impl Player {
+    pub const MAX_FPS: u32 = 1024;
+
+    pub fn new(frames: usize, fps: u32) -> Player {
+        Player {
+            frame: 0,
+            frames,
+            paused: false,
+            fps: fps.clamp(1, Player::MAX_FPS),
+        }
+    }
+
+    /// How long each frame stays on screen while playing.
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(1) / self.fps
+    }
+
+    fn last(&self) -> usize {
+        self.frames.saturating_sub(1)
+    }
+
+    /// Moves on to the next frame unless paused, pausing at the end.
+    pub fn tick(&mut self) {
+        if !self.paused {
+            self.frame = (self.frame + 1).min(self.last());
+            self.paused = self.frame == self.last();
+        }
+    }
+
+    pub fn control(&mut self, control: Control) {
+        match control {
+            Control::Pause if self.paused && self.frame == self.last() => {
+                self.frame = 0;
+                self.paused = false;
+            }
+            Control::Pause => self.paused = !self.paused,
+            Control::Step => {
+                self.paused = true;
+                self.frame = (self.frame + 1).min(self.last());
+            }
+            Control::Back => {
+                self.paused = true;
+                self.frame = self.frame.saturating_sub(1);
+            }
+            Control::Rewind => self.frame = 0,
+            Control::End => self.frame = self.last(),
+            Control::Faster => self.fps = (self.fps * 2).min(Player::MAX_FPS),
+            Control::Slower => self.fps = (self.fps / 2).max(1),
+            Control::Scroll(..) | Control::Quit => {}
+        }
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "{}/{}  {} fps{}",
+            self.frame + 1,
+            self.frames,
+            self.fps,
+            if self.paused { "  paused" } else { "" }
+        )
+    }
+}
+
+/// Plays `replay` in the terminal until the viewer quits.
+pub fn play(day: &Day, replay: &dyn Replay, fps: u32) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let played = play_in(&mut terminal, day, replay, fps);
+    ratatui::restore();
+    played
+}
+
+fn play_in(
+    terminal: &mut DefaultTerminal,
+    day: &Day,
+    replay: &dyn Replay,
+    fps: u32,
+) -> io::Result<()> {
+    let mut player = Player::new(replay.len(), fps);
+    let mut scroll = (0u16, 0u16);
+    let mut next_tick = Instant::now() + player.interval();
+    loop {
+        let text = replay.render(player.frame);
+        let title = format!(
+            " day{} · {} · {} ",
+            day.number,
+            replay.caption(player.frame),
+            player.status()
+        );
+        terminal.draw(|frame| {
+            let [grid, help] =
+                Layout::vertical([Constraint::Min(1), Constraint::Length(1)])
+                    .areas(frame.area());
+            frame.render_widget(
+                Paragraph::new(text)
+                    .scroll(scroll)
+                    .block(Block::bordered().title(Line::from(title))),
+                grid,
+            );
+            frame.render_widget(Line::from(HELP), help);
+        })?;
+
+        let timeout = next_tick.saturating_duration_since(Instant::now());
+        if event::poll(timeout)? {
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match Control::of(key.code) {
+                Some(Control::Quit) => return Ok(()),
+                Some(Control::Scroll(dm, dn)) => {
+                    scroll.0 = scroll.0.saturating_add_signed(dm as i16);
+                    scroll.1 = scroll.1.saturating_add_signed(dn as i16);
+                }
+                Some(control) => player.control(control),
+                None => {}
+            }
+        } else {
+            player.tick();
+            next_tick = Instant::now() + player.interval();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_player() {
+        let mut player = Player::new(3, 10);
+        assert_eq!(player.interval(), Duration::from_millis(100));
+        player.tick();
+        player.tick();
+        player.tick();
+        assert_eq!((player.frame, player.paused), (2, true));
+        player.control(Control::Back);
+        player.control(Control::Back);
+        player.control(Control::Back);
+        assert_eq!(player.frame, 0);
+        player.control(Control::Step);
+        player.tick();
+        assert_eq!((player.frame, player.paused), (1, true));
+        player.control(Control::End);
+        player.control(Control::Pause);
+        assert_eq!((player.frame, player.paused), (0, false));
+        player.control(Control::Slower);
+        player.control(Control::Slower);
+        player.control(Control::Slower);
+        player.control(Control::Slower);
+        assert_eq!(player.fps, 1);
+        player.control(Control::Faster);
+        assert_eq!(player.fps, 2);
+    }
+
+    #[test]
+    fn test_record() {
+        let day = crate::day(16).unwrap();
+        let input = ".\\\r\n..\r\n";
+        let replay = record(&day, input).unwrap().unwrap();
+        assert_eq!(replay.render(replay.len() - 1), "##\n.#\n");
+        assert!(record(&crate::day(1).unwrap(), input).is_none());
+    }
+
+    #[test]
+    fn test_picture() {
+        let day = crate::day(14).unwrap();
+        let picture = picture(&day, "O.\r\n.#\r\n").unwrap().unwrap();
+        assert_eq!(picture.size(), (2, 2));
+        assert!(picture.to_svg(4).contains("<circle "));
+        assert!(super::picture(&crate::day(1).unwrap(), "").is_none());
+    }
+}
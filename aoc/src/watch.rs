@@ -0,0 +1,288 @@
+//! Re-runs a day whenever its sources, examples or input change: rebuilds
+//! it, runs its tests and solves the real input through the runner, then
+//! shows how the answers moved since the previous run.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
+
+use common::Part;
+use toml::{Table, Value};
+
+use crate::{workspace_root, Day};
+
+/// What to watch for a day: its sources and input, its examples and its
+/// manifest, then the sources and manifests of the workspace crates it
+/// depends on, such as grid or parsers.
+pub fn watched(day: &Day) -> Vec<PathBuf> {
+    let dir = day.dir();
+    let mut paths = vec![
+        dir.join("src"),
+        dir.join("examples"),
+        dir.join("Cargo.toml"),
+    ];
+    for dependency in path_dependencies(&dir) {
+        paths.push(dependency.join("src"));
+        paths.push(dependency.join("Cargo.toml"));
+    }
+    paths
+}
+
+/// The crates `dir`'s crate depends on by path, directly or through one
+/// another, tests included. Unreadable manifests count as having none.
+fn path_dependencies(dir: &Path) -> BTreeSet<PathBuf> {
+    let root = workspace_root();
+    let manifest = |dir: &Path| {
+        fs::read_to_string(dir.join("Cargo.toml"))
+            .ok()
+            .and_then(|m| m.parse::<Table>().ok())
+            .unwrap_or_default()
+    };
+    let workspace = manifest(root);
+    let shared = workspace
+        .get("workspace")
+        .and_then(|w| w.get("dependencies"))
+        .and_then(Value::as_table);
+    let mut found = BTreeSet::new();
+    let mut queue = vec![dir.to_path_buf()];
+    while let Some(dir) = queue.pop() {
+        let manifest = manifest(&dir);
+        let sections =
+            ["dependencies", "dev-dependencies", "build-dependencies"];
+        let dependencies = sections
+            .iter()
+            .filter_map(|section| manifest.get(*section)?.as_table())
+            .flatten();
+        for (name, dependency) in dependencies {
+            let path = match dependency.get("path").and_then(Value::as_str) {
+                Some(path) => dir.join(path),
+                None if dependency.get("workspace")
+                    == Some(&Value::Boolean(true)) =>
+                {
+                    let Some(path) = shared
+                        .and_then(|s| s.get(name)?.get("path")?.as_str())
+                    else {
+                        continue;
+                    };
+                    root.join(path)
+                }
+                None => continue,
+            };
+            let path = path.canonicalize().unwrap_or(path);
+            if found.insert(path.clone()) {
+                queue.push(path);
+            }
+        }
+    }
+    found
+}
+
+/// When each file under some paths was last modified, and its size, so
+/// edits show up by comparing two scans.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Stamps(BTreeMap<PathBuf, (SystemTime, u64)>);
+
+impl Stamps {
+    /// Every file under `paths`, skipping those that do not exist.
+    pub fn scan(paths: &[PathBuf]) -> Stamps {
+        let mut stamps = Stamps::default();
+        for path in paths {
+            stamps.add(path);
+        }
+        stamps
+    }
+
+    fn add(&mut self, path: &Path) {
+        let Ok(metadata) = fs::metadata(path) else {
+            return;
+        };
+        if metadata.is_dir() {
+            for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+                self.add(&entry.path());
+            }
+        } else if let Ok(modified) = metadata.modified() {
+            self.0
+                .insert(path.to_path_buf(), (modified, metadata.len()));
+        }
+    }
+
+    /// The files added, removed or modified since `before`.
+    pub fn changed(&self, before: &Stamps) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = self
+            .0
+            .iter()
+            .filter(|(path, stamp)| before.0.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            before
+                .0
+                .keys()
+                .filter(|path| !self.0.contains_key(*path))
+                .cloned(),
+        );
+        changed.sort();
+        changed
+    }
+}
+
+/// Runs cargo in the workspace, its output going straight to the terminal.
+fn cargo(args: &[&str]) -> Result<(), String> {
+    let status = Command::new(env!("CARGO"))
+        .args(args)
+        .current_dir(workspace_root())
+        .status()
+        .map_err(|e| format!("cannot run cargo: {e}"))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("`cargo {}` failed", args.join(" ")))
+    }
+}
+
+/// Rebuilds the day and runs its tests, then rebuilds the runner with it
+/// and returns what `aoc run` answers on the real input, one answer per
+/// part.
+pub fn check(day: &Day) -> Result<Vec<String>, String> {
+    let number = day.number.to_string();
+    cargo(&["test", "--quiet", "-p", &format!("day{number}")])?;
+    let output = Command::new(env!("CARGO"))
+        .args(["run", "--release", "--quiet", "-p", "aoc", "--"])
+        .args(["run", &number])
+        .current_dir(workspace_root())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("cannot run cargo: {e}"))?;
+    if !output.status.success() {
+        return Err("`aoc run` failed on the real input".to_string());
+    }
+    Ok(answers(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// The answers in what `aoc run` prints, a `dayN partK: ANSWER (TIME)` line
+/// per part.
+fn answers(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once(": ")?;
+            let (answer, _) = rest.rsplit_once(" (")?;
+            Some(answer.trim_end().to_string())
+        })
+        .collect()
+}
+
+/// One line per part: its answer, how it changed since `before` and
+/// whether it is the accepted one from answers.toml.
+pub fn report(
+    day: &Day,
+    before: Option<&[String]>,
+    after: &[String],
+) -> Vec<String> {
+    let parts = [Part::Part1, Part::Part2];
+    after
+        .iter()
+        .zip(parts)
+        .enumerate()
+        .map(|(i, (answer, part))| {
+            let mut line = match before.and_then(|b| b.get(i)) {
+                None => format!("{part}: {answer}"),
+                Some(previous) if previous == answer => {
+                    format!("{part}: {answer} (unchanged)")
+                }
+                Some(previous) => format!("{part}: {previous} -> {answer}"),
+            };
+            match day.expected(part) {
+                Ok(Some(expected)) if expected.to_string() == *answer => {
+                    line += ", accepted";
+                }
+                Ok(Some(expected)) => {
+                    line += &format!(", expected {expected}")
+                }
+                _ => {}
+            }
+            line
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day;
+
+    #[test]
+    fn test_stamps() {
+        let dir = std::env::temp_dir()
+            .join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+        let (lib, input) = (dir.join("src/lib.rs"), dir.join("input.txt"));
+        fs::write(&lib, "fn a() {}").unwrap();
+        let paths = vec![dir.join("src"), input.clone(), dir.join("missing")];
+        let before = Stamps::scan(&paths);
+        assert_eq!(before.0.len(), 1);
+        assert!(Stamps::scan(&paths).changed(&before).is_empty());
+
+        fs::write(&input, "1 2 3").unwrap();
+        fs::write(&lib, "fn a() { 1; }").unwrap();
+        let after = Stamps::scan(&paths);
+        assert_eq!(after.changed(&before), [input.clone(), lib.clone()]);
+
+        fs::remove_file(&input).unwrap();
+        assert_eq!(Stamps::scan(&paths).changed(&after), [input]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_watched() {
+        let paths = watched(&day(12).unwrap());
+        assert!(paths[0].ends_with("day12/src"));
+        assert!(Stamps::scan(&paths)
+            .0
+            .keys()
+            .any(|path| path.ends_with("day12/src/input.txt")));
+    }
+
+    #[test]
+    fn test_path_dependencies() {
+        let paths = watched(&day(5).unwrap());
+        for dependency in ["common", "intervals", "parsers"] {
+            let src = workspace_root().join(dependency).join("src");
+            assert!(paths.contains(&src.canonicalize().unwrap()), "{src:?}");
+        }
+        assert!(!paths.iter().any(|path| path.ends_with("grid/src")));
+        let crates = path_dependencies(&workspace_root().join("aoc"));
+        assert!(crates.iter().any(|path| path.ends_with("day16")));
+        assert!(crates.iter().any(|path| path.ends_with("search")));
+    }
+
+    #[test]
+    fn test_answers() {
+        let output = "day6  part1: 170000               (5.25µs)\n\
+                      day6  part2: a b (c)              (1.78µs)\n";
+        assert_eq!(answers(output), ["170000", "a b (c)"]);
+    }
+
+    #[test]
+    fn test_report() {
+        let day = day(8).unwrap();
+        let first = ["21797".to_string(), "1".to_string()];
+        assert_eq!(
+            report(&day, None, &first),
+            [
+                "part1: 21797, accepted",
+                "part2: 1, expected 23977527174353"
+            ]
+        );
+        let second = ["21797".to_string(), "23977527174353".to_string()];
+        assert_eq!(
+            report(&day, Some(&first), &second),
+            [
+                "part1: 21797 (unchanged), accepted",
+                "part2: 1 -> 23977527174353, accepted"
+            ]
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use std::{fs, thread};
+
+use aoc::days;
+use common::Part;
+
+#[test]
+fn test_every_day_has_answers() {
+    for day in days() {
+        assert!(
+            day.answers_path().exists(),
+            "day{} has no answers.toml",
+            day.number
+        );
+    }
+}
+
+/// Solves every registered day's real input, one thread per part, and
+/// reports all parts that drifted from answers.toml at once.
+#[test]
+fn test_answers() {
+    let days = days();
+    let mismatches: Vec<String> = thread::scope(|scope| {
+        let handles: Vec<_> = days
+            .iter()
+            .flat_map(|day| [(day, Part::Part1), (day, Part::Part2)])
+            .map(|(day, part)| {
+                scope.spawn(move || {
+                    let expected = day.expected(part).unwrap()?;
+                    let input = fs::read_to_string(day.input_path()).unwrap();
+                    let answer = day.solve(&input, part).unwrap();
+                    (answer != expected).then(|| {
+                        format!(
+                            "day{} {}: {} != {}",
+                            day.number, part, answer, expected
+                        )
+                    })
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|h| h.join().expect("solver should not panic"))
+            .collect()
+    });
+    assert!(
+        mismatches.is_empty(),
+        "no longer matches answers.toml:\n{}",
+        mismatches.join("\n")
+    );
+}
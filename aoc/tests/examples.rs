@@ -0,0 +1,50 @@
+use std::fs;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use aoc::{days, examples};
+
+/// The same input as saved on Windows, padded with trailing spaces and
+/// blank lines, and without its final newline, none of which the parsers
+/// support before `Solution::solve` normalizes it.
+fn unnormalized(input: &str) -> [(&'static str, String); 4] {
+    [
+        ("LF", input.to_string()),
+        ("CRLF", input.replace('\n', "\r\n")),
+        (
+            "padded",
+            input.replace('\n', "  \n").replace("  \n  \n", "  \n\t\n")
+                + "\n \n",
+        ),
+        ("unterminated", input.trim_end().to_string()),
+    ]
+}
+
+/// Solves every example listed in a day's examples/expected.toml through
+/// `Day::solve`, in each of its unnormalized forms.
+#[test]
+fn test_examples_normalized_by_solve() {
+    let mut mismatches = vec![];
+    for day in days() {
+        for case in examples::load(&day).unwrap() {
+            let input = fs::read_to_string(&case.input).unwrap();
+            for (variant, input) in unnormalized(&input) {
+                let answer = catch_unwind(AssertUnwindSafe(|| {
+                    day.solve(&input, case.part)
+                }))
+                .map_err(|_| "panicked");
+                if answer.as_ref().map(|a| a.as_ref()) != Ok(Ok(&case.answer))
+                {
+                    mismatches.push(format!(
+                        "{} {} ({}): {:?} != {}",
+                        case.input.display(),
+                        case.part,
+                        variant,
+                        answer,
+                        case.answer
+                    ));
+                }
+            }
+        }
+    }
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
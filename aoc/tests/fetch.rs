@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+use std::{fs, process};
+
+use aoc::fetch::{FetchError, Fetcher};
+use tiny_http::{Response, Server};
+
+/// A stand-in for the puzzle site answering every request with `status` and
+/// `body`, reporting each requested url and session cookie.
+fn serve(status: u16, body: &'static str) -> (String, Receiver<String>) {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr());
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let cookie = request
+                .headers()
+                .iter()
+                .find(|h| h.field.equiv("Cookie"))
+                .map(|h| h.value.to_string())
+                .unwrap_or_default();
+            tx.send(format!("{} {}", request.url(), cookie)).unwrap();
+            let response =
+                Response::from_string(body).with_status_code(status);
+            request.respond(response).unwrap();
+        }
+    });
+    (url, rx)
+}
+
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "aoc-fetch-{}-{}",
+        name,
+        process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn fetcher(url: &str, dir: &Path) -> Fetcher {
+    Fetcher::new(url, Some("abc".to_string()), 2023, &dir.join("cache"))
+        .with_min_interval(Duration::ZERO)
+}
+
+#[test]
+fn test_fetch_then_cache() {
+    let (url, requests) = serve(200, "1 2 3\n");
+    let dir = scratch("cache");
+    let dest = dir.join("input.txt");
+    let fetcher = fetcher(&url, &dir);
+
+    fetcher.install(7, &dest, false).unwrap();
+    assert_eq!(fs::read_to_string(&dest).unwrap(), "1 2 3\n");
+    assert_eq!(requests.recv().unwrap(), "/2023/day/7/input session=abc");
+
+    fs::remove_file(&dest).unwrap();
+    fetcher.install(7, &dest, false).unwrap();
+    assert_eq!(fs::read_to_string(&dest).unwrap(), "1 2 3\n");
+    assert!(requests.try_recv().is_err());
+
+    fetcher.install(7, &dest, true).unwrap();
+    assert!(requests.recv_timeout(Duration::from_secs(5)).is_ok());
+}
+
+#[test]
+fn test_fetch_error_keeps_input() {
+    let (url, _requests) = serve(500, "oops");
+    let dir = scratch("status");
+    let dest = dir.join("input.txt");
+    fs::write(&dest, "old").unwrap();
+
+    let err = fetcher(&url, &dir).install(1, &dest, true).unwrap_err();
+    assert!(matches!(err, FetchError::Status(500)));
+    assert_eq!(fs::read_to_string(&dest).unwrap(), "old");
+    assert!(!fetcher(&url, &dir).cache_path(1).exists());
+}
+
+#[test]
+fn test_fetch_logged_out() {
+    let (url, _requests) = serve(
+        200,
+        "Puzzle inputs differ by user.  Please log in to get your input.",
+    );
+    let dir = scratch("login");
+    let dest = dir.join("input.txt");
+    fs::write(&dest, "old").unwrap();
+
+    let err = fetcher(&url, &dir).install(1, &dest, false).unwrap_err();
+    assert!(matches!(err, FetchError::LoggedOut));
+    assert_eq!(fs::read_to_string(&dest).unwrap(), "old");
+}
+
+#[test]
+fn test_fetch_without_session() {
+    let (url, requests) = serve(200, "1 2 3\n");
+    let dir = scratch("session");
+    let fetcher = Fetcher::new(&url, None, 2023, &dir.join("cache"));
+
+    let err = fetcher.input(1, false).unwrap_err();
+    assert!(matches!(err, FetchError::MissingSession));
+    assert!(requests.try_recv().is_err());
+}
+
+#[test]
+fn test_fetch_puzzle_page_without_session() {
+    let (url, requests) = serve(200, "<article>Please log in</article>");
+    let dir = scratch("anonymous");
+    let fetcher = Fetcher::new(&url, None, 2023, &dir.join("cache"))
+        .with_min_interval(Duration::ZERO);
+
+    let page = fetcher.puzzle(3, false).unwrap();
+    assert_eq!(page, "<article>Please log in</article>");
+    assert_eq!(requests.recv().unwrap(), "/2023/day/3 ");
+}
+
+#[test]
+fn test_fetch_rate_limit() {
+    let (url, _requests) = serve(200, "1 2 3\n");
+    let dir = scratch("rate");
+    let interval = Duration::from_millis(300);
+    let fetcher = fetcher(&url, &dir).with_min_interval(interval);
+
+    let start = Instant::now();
+    fetcher.input(1, true).unwrap();
+    fetcher.input(2, true).unwrap();
+    fetcher.input(3, true).unwrap();
+    assert!(start.elapsed() >= interval * 2);
+}
+
+#[test]
+fn test_fetch_puzzle_page() {
+    let (url, requests) = serve(200, "<article>Please log in</article>");
+    let dir = scratch("puzzle");
+    let fetcher = fetcher(&url, &dir);
+
+    let page = fetcher.puzzle(9, false).unwrap();
+    assert_eq!(page, "<article>Please log in</article>");
+    assert_eq!(requests.recv().unwrap(), "/2023/day/9 session=abc");
+    assert!(fetcher.puzzle_cache_path(9).ends_with("2023/day9.html"));
+    assert_eq!(fetcher.puzzle(9, false).unwrap(), page);
+    assert!(requests.try_recv().is_err());
+}
@@ -0,0 +1,54 @@
+"""Runs a day's Python prototype on input files for aoc/tests/parity.rs.
+
+    python3 aoc/tests/parity.py day1/src/main.py day1/examples/example1.txt
+
+Prints one JSON line per input file with the answers to both parts, null
+where the prototype raised. Prototypes either define `part1` and `part2`,
+or a `main(input)` returning both answers.
+"""
+import importlib.util
+import json
+import sys
+import types
+
+try:
+    import pytest  # noqa: F401
+except ImportError:
+    # The prototypes only use pytest for their test fixtures.
+    sys.modules["pytest"] = types.SimpleNamespace(fixture=lambda f: f)
+
+
+def load(script):
+    spec = importlib.util.spec_from_file_location("prototype", script)
+    module = importlib.util.module_from_spec(spec)
+    spec.loader.exec_module(module)
+    return module
+
+
+def attempt(solve):
+    try:
+        return solve()
+    except Exception:
+        return None
+
+
+def answers(module, text):
+    if hasattr(module, "part1"):
+        return [
+            attempt(lambda: module.part1(text)),
+            attempt(lambda: module.part2(text)),
+        ]
+    both = attempt(lambda: module.main(text))
+    return list(both) if both else [None, None]
+
+
+def main():
+    script, *paths = sys.argv[1:]
+    module = load(script)
+    for path in paths:
+        with open(path) as f:
+            print(json.dumps(answers(module, f.read())))
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,218 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{fs, process};
+
+use aoc::{day, examples, workspace_root};
+use common::{Answer, Part};
+use serde_json::Value;
+
+/// Generated inputs per day, on top of its examples and real input.
+const GENERATED: usize = 50;
+
+/// A small deterministic generator, so a failing input can be regenerated.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn range(&mut self, low: usize, high: usize) -> usize {
+        low + self.below(high - low + 1)
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+/// Calibration lines mixing letters, digits and spelled out digits, some
+/// overlapping like `eightwo`, always with at least one plain digit.
+fn calibration(rng: &mut Rng) -> String {
+    let words = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight",
+        "nine", "twone", "eightwo", "oneight", "sevenine",
+    ];
+    let mut lines = vec![];
+    for _ in 0..rng.range(1, 30) {
+        let mut line = String::new();
+        for _ in 0..rng.range(0, 6) {
+            match rng.below(3) {
+                0 => {
+                    let word = rng.pick(&words);
+                    line.push_str(word)
+                }
+                1 => line.push((b'a' + rng.below(26) as u8) as char),
+                _ => line.push((b'1' + rng.below(9) as u8) as char),
+            }
+        }
+        let at = rng.below(line.len() + 1);
+        line.insert(at, (b'1' + rng.below(9) as u8) as char);
+        lines.push(line);
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Games of one to four handfuls of up to twenty cubes of each color.
+fn games(rng: &mut Rng) -> String {
+    let mut lines = vec![];
+    for id in 1..=rng.range(1, 30) {
+        let sets: Vec<String> = (0..rng.range(1, 4))
+            .map(|_| {
+                let mut colors = vec!["red", "green", "blue"];
+                colors.rotate_left(rng.below(3));
+                colors.truncate(rng.range(1, 3));
+                colors
+                    .iter()
+                    .map(|color| format!("{} {}", rng.range(1, 20), color))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        lines.push(format!("Game {}: {}", id, sets.join("; ")));
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Engine schematics of numbers up to three digits among dots and symbols.
+fn schematic(rng: &mut Rng) -> String {
+    let (width, height) = (rng.range(3, 15), rng.range(1, 15));
+    let mut rows = vec![];
+    for _ in 0..height {
+        let mut row = String::new();
+        while row.len() < width {
+            match rng.below(10) {
+                0..=2 if !row.ends_with(|c: char| c.is_ascii_digit()) => {
+                    let digits = rng.range(1, 3).min(width - row.len());
+                    for _ in 0..digits {
+                        row.push((b'0' + rng.below(10) as u8) as char);
+                    }
+                }
+                3 => row.push(*rng.pick(&['*', '#', '+', '$', '/', '@', '-'])),
+                _ => row.push('.'),
+            }
+        }
+        rows.push(row);
+    }
+    rows.join("\n") + "\n"
+}
+
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "aoc-parity-{}-{}",
+        name,
+        process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// The prototype's answers to both parts of each input, `None` where it
+/// raised.
+fn prototype(script: &Path, inputs: &[PathBuf]) -> Vec<[Option<i64>; 2]> {
+    let driver = workspace_root().join("aoc/tests/parity.py");
+    let output = Command::new("python3")
+        .arg(driver)
+        .arg(script)
+        .args(inputs)
+        .output()
+        .expect("python3 should run");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| {
+            let answers: Vec<Value> = serde_json::from_str(line).unwrap();
+            [answers[0].as_i64(), answers[1].as_i64()]
+        })
+        .collect()
+}
+
+/// Solves each input with the Python prototype in dayN/src/main.py and with
+/// the Rust solution, and checks the answers match wherever the prototype
+/// gives one. Returns how many answers were compared.
+fn check_parity(number: u8, generate: fn(&mut Rng) -> String) -> usize {
+    let day = day(number).unwrap();
+    let dir = scratch(&format!("day{}", number));
+    let mut inputs = examples::files(&day);
+    if day.input_path().exists() {
+        inputs.push(day.input_path());
+    }
+    let mut rng = Rng(0x2023_1201 + number as u64);
+    for i in 0..GENERATED {
+        let path = dir.join(format!("generated{}.txt", i));
+        fs::write(&path, generate(&mut rng)).unwrap();
+        inputs.push(path);
+    }
+
+    let script = day.dir().join("src/main.py");
+    let expected = prototype(&script, &inputs);
+    assert_eq!(expected.len(), inputs.len());
+    let mut compared = 0;
+    let mut mismatches = vec![];
+    for (path, expected) in inputs.iter().zip(expected) {
+        let input = fs::read_to_string(path).unwrap();
+        for (part, expected) in
+            [Part::Part1, Part::Part2].into_iter().zip(expected)
+        {
+            let Some(expected) = expected else {
+                continue;
+            };
+            let answer = day.solve(&input, part);
+            if answer != Ok(Answer::from(expected)) {
+                mismatches.push(format!(
+                    "{} {}: python {} != rust {:?}",
+                    path.display(),
+                    part,
+                    expected,
+                    answer
+                ));
+            }
+            compared += 1;
+        }
+    }
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+    let _ = fs::remove_dir_all(&dir);
+    compared
+}
+
+fn has_python() -> bool {
+    let found = Command::new("python3").arg("--version").output().is_ok();
+    if !found {
+        eprintln!("python3 not found, skipping the prototype parity checks");
+    }
+    found
+}
+
+#[test]
+fn test_day1_parity() {
+    if has_python() {
+        assert!(check_parity(1, calibration) >= 2 * GENERATED);
+    }
+}
+
+#[test]
+fn test_day2_parity() {
+    if has_python() {
+        assert!(check_parity(2, games) >= 2 * GENERATED);
+    }
+}
+
+#[test]
+fn test_day3_parity() {
+    if has_python() {
+        assert!(check_parity(3, schematic) >= 2 * GENERATED);
+    }
+}
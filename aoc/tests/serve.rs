@@ -0,0 +1,35 @@
+use std::thread;
+
+use aoc::serve::serve;
+use serde_json::Value;
+use tiny_http::Server;
+
+fn json(response: ureq::Response) -> Value {
+    serde_json::from_str(&response.into_string().unwrap()).unwrap()
+}
+
+#[test]
+fn test_serve() {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", server.server_addr());
+    thread::spawn(move || serve(server));
+
+    let page = ureq::get(&url).call().unwrap();
+    assert_eq!(page.content_type(), "text/html");
+    assert!(page.into_string().unwrap().contains("/api/days"));
+
+    let states = json(
+        ureq::get(&format!("{url}/api/day/10/example1"))
+            .call()
+            .unwrap(),
+    );
+    assert_eq!(states["day"], 10);
+    assert_eq!(states["states"][0]["layers"][0]["name"], "loop");
+
+    match ureq::get(&format!("{url}/api/day/10/nope")).call() {
+        Err(ureq::Error::Status(404, response)) => {
+            assert_eq!(json(response)["error"], "no input called nope");
+        }
+        other => panic!("expected a 404, got {other:?}"),
+    }
+}
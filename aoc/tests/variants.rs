@@ -0,0 +1,37 @@
+use std::fs;
+
+use aoc::variants::{compare, of};
+use aoc::{days, examples};
+use common::Part;
+
+/// Runs every variant on every example, checking that they agree with each
+/// other and that the first one is what the day's `Solution` answers.
+#[test]
+fn test_variants_agree() {
+    let mut mismatches = vec![];
+    for day in days() {
+        for path in examples::files(&day) {
+            let input = fs::read_to_string(&path).unwrap();
+            for part in [Part::Part1, Part::Part2] {
+                let Some(comparison) = compare(&day, part, &input) else {
+                    continue;
+                };
+                let solved =
+                    day.solve(&input, part).map_err(|e| e.to_string());
+                let first = of(&day, part)[0].solve(&input);
+                if !comparison.agrees()
+                    || first.map_err(|e| e.to_string()) != solved
+                {
+                    mismatches.push(format!(
+                        "{} {}: {} (solved {:?})",
+                        path.display(),
+                        part,
+                        comparison,
+                        solved
+                    ));
+                }
+            }
+        }
+    }
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
@@ -0,0 +1,240 @@
+use std::fmt::{Display, Formatter};
+use std::process;
+
+use nom::error::{Error, ErrorKind};
+use nom::IResult;
+
+/// Malformed puzzle input, pointing at the offending line and column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: String,
+    line: usize,
+    column: usize,
+    snippet: String,
+}
+
+impl ParseError {
+    /// An error at byte `offset` of `input`.
+    pub fn at(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        ParseError {
+            message: message.into(),
+            line: before.matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end].trim_end().to_string(),
+        }
+    }
+
+    /// An error where `rest`, a slice of `input`, starts.
+    pub fn at_slice(
+        input: &str,
+        rest: &str,
+        message: impl Into<String>,
+    ) -> Self {
+        let offset = (rest.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .unwrap_or(input.len());
+        ParseError::at(input, offset, message)
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Prints the diagnostic and exits, for day binaries.
+    pub fn exit(&self) -> ! {
+        eprintln!("{}", self);
+        process::exit(1)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{}--> input:{}:{}", gutter, self.line, self.column)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.snippet)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(self.column - 1))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Why a part could not be answered: the input is malformed, or it parses
+/// but the puzzle it describes has no answer, such as a path that never
+/// arrives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    Malformed(ParseError),
+    NoAnswer(String),
+}
+
+impl SolveError {
+    pub fn no_answer(message: impl Into<String>) -> Self {
+        SolveError::NoAnswer(message.into())
+    }
+
+    /// Prints the diagnostic and exits, for day binaries.
+    pub fn exit(&self) -> ! {
+        eprintln!("{}", self);
+        process::exit(1)
+    }
+}
+
+impl From<ParseError> for SolveError {
+    fn from(err: ParseError) -> Self {
+        SolveError::Malformed(err)
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Malformed(err) => Display::fmt(err, f),
+            SolveError::NoAnswer(message) => {
+                write!(f, "error: no answer, {}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Turns the result of a nom parser run on (a slice of) `input` into its
+/// output, failing on parse errors and on anything but whitespace left over.
+pub fn finish<'a, T>(
+    input: &'a str,
+    result: IResult<&'a str, T, Error<&'a str>>,
+) -> Result<T, ParseError> {
+    match result {
+        Ok((rest, _)) if !rest.trim().is_empty() => Err(ParseError::at_slice(
+            input,
+            rest,
+            "unexpected trailing input",
+        )),
+        Ok((_, output)) => Ok(output),
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+            Err(ParseError::at_slice(input, err.input, expected(err.code)))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at(
+            input,
+            input.len(),
+            "unexpected end of input",
+        )),
+    }
+}
+
+/// Checks that `lines`, slices of `input`, make a non-empty rectangle of
+/// `tiles`, as the grid puzzles expect.
+pub fn check_grid<'a>(
+    input: &'a str,
+    lines: impl IntoIterator<Item = &'a str>,
+    tiles: &str,
+) -> Result<(), ParseError> {
+    let mut width = None;
+    for line in lines {
+        if let Some(i) = line.find(|c| !tiles.contains(c)) {
+            let found = line[i..].chars().next().expect("should be a char");
+            return Err(ParseError::at_slice(
+                input,
+                &line[i..],
+                format!("expected one of {:?}, not {:?}", tiles, found),
+            ));
+        }
+        match width {
+            None => width = Some(line.len()),
+            Some(width) if width != line.len() => {
+                return Err(ParseError::at_slice(
+                    input,
+                    &line[width.min(line.len())..],
+                    format!("expected {} tiles in every row", width),
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+    match width {
+        Some(width) if width > 0 => Ok(()),
+        _ => Err(ParseError::at(input, 0, "expected a grid")),
+    }
+}
+
+fn expected(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Digit => "expected a number".to_string(),
+        ErrorKind::Alpha => "expected a word".to_string(),
+        ErrorKind::AlphaNumeric => "expected a name".to_string(),
+        ErrorKind::Space | ErrorKind::MultiSpace => {
+            "expected whitespace".to_string()
+        }
+        ErrorKind::CrLf => "expected a line break".to_string(),
+        ErrorKind::Tag => "unexpected input".to_string(),
+        ErrorKind::MapOpt => "number out of range".to_string(),
+        kind => format!("unexpected input ({})", kind.description()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::bytes::complete::tag;
+    use nom::character::complete::u32 as nom_u32;
+    use nom::sequence::preceded;
+
+    use super::*;
+
+    #[test]
+    fn test_at() {
+        let input = "Game 1: 3 blue\nGame 2: x red\n";
+        let err = ParseError::at(input, 23, "expected a number");
+        assert_eq!((err.line(), err.column()), (2, 9));
+        assert_eq!(
+            err.to_string(),
+            "error: expected a number\n \
+             --> input:2:9\n  \
+             |\n\
+             2 | Game 2: x red\n  \
+             |         ^"
+        );
+    }
+
+    #[test]
+    fn test_check_grid() {
+        let check = |input| check_grid(input, input.lines(), ".#");
+        assert_eq!(check("#.\n.#\n"), Ok(()));
+        let err = check("#.\n.O\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 2));
+        let err = check("#.\n.\n").unwrap_err();
+        assert_eq!(err.message(), "expected 2 tiles in every row");
+        assert_eq!((err.line(), err.column()), (2, 2));
+        assert!(check("").is_err());
+    }
+
+    #[test]
+    fn test_finish() {
+        let input = "a\nGame 7x";
+        let line = &input[2..];
+        let parse = |s| preceded(tag("Game "), nom_u32)(s);
+        assert_eq!(finish(input, parse("Game 7 ")), Ok(7));
+
+        let err = finish(input, parse(line)).unwrap_err();
+        assert_eq!(err.message(), "unexpected trailing input");
+        assert_eq!((err.line(), err.column()), (2, 7));
+
+        let err = finish(input, parse(&line[1..])).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 2));
+    }
+}
@@ -0,0 +1,203 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::io::{IsTerminal, Read};
+use std::path::PathBuf;
+use std::{fs, io, process};
+
+/// The `input.txt` next to the calling file, when the calling crate is
+/// built with its `embedded-input` feature, `None` otherwise.
+#[macro_export]
+macro_rules! embedded_input {
+    () => {{
+        #[cfg(feature = "embedded-input")]
+        let embedded: Option<&'static str> = Some(include_str!("input.txt"));
+        #[cfg(not(feature = "embedded-input"))]
+        let embedded: Option<&'static str> = None;
+        embedded
+    }};
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    Embedded(&'static str),
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Usage(String),
+    Missing,
+    Io(PathBuf, io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Usage(arg) => {
+                write!(f, "unexpected argument `{}`\n\n{}", arg, USAGE)
+            }
+            InputError::Missing => write!(
+                f,
+                "no puzzle input given\n\n{}\n\nor build with \
+                 `--features embedded-input` to fall back to src/input.txt",
+                USAGE
+            ),
+            InputError::Io(path, err) => {
+                write!(f, "cannot read {}: {}", path.display(), err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+const USAGE: &str = "usage: <day> [--input <PATH> | <PATH> | -]";
+
+/// Picks where the puzzle input comes from: an explicit path (`-` meaning
+/// stdin), then piped stdin, then the embedded copy.
+pub fn source<I>(
+    args: I,
+    stdin_is_terminal: bool,
+    embedded: Option<&'static str>,
+) -> Result<Source, InputError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    let path = match args.next() {
+        Some(flag) if flag == "--input" || flag == "-i" => {
+            Some(args.next().ok_or(InputError::Usage(flag))?)
+        }
+        Some(arg) if arg.starts_with('-') && arg != "-" => {
+            return Err(InputError::Usage(arg));
+        }
+        arg => arg,
+    };
+    if let Some(extra) = args.next() {
+        return Err(InputError::Usage(extra));
+    }
+
+    match (path, embedded) {
+        (Some(path), _) if path == "-" => Ok(Source::Stdin),
+        (Some(path), _) => Ok(Source::File(path.into())),
+        (None, _) if !stdin_is_terminal => Ok(Source::Stdin),
+        (None, Some(embedded)) => Ok(Source::Embedded(embedded)),
+        (None, None) => Err(InputError::Missing),
+    }
+}
+
+/// The input with `\r\n` line endings, trailing whitespace and trailing
+/// blank lines removed, so it ends in exactly one newline. This is the only
+/// form `Solution::parse` and the days' own functions support.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let mut normalized = String::with_capacity(input.len());
+    for line in input.trim_end().lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    if normalized == input {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+impl Source {
+    /// Reads the whole input, normalized.
+    pub fn read(self) -> Result<String, InputError> {
+        let input = match self {
+            Source::File(path) => {
+                fs::read_to_string(&path).map_err(|e| InputError::Io(path, e))
+            }
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| InputError::Io("<stdin>".into(), e))?;
+                Ok(input)
+            }
+            Source::Embedded(input) => Ok(input.to_string()),
+        }?;
+        Ok(normalize(&input).into_owned())
+    }
+}
+
+/// Reads the puzzle input for a day binary from its command line, exiting
+/// with a usage message when there is none.
+pub fn load_or_exit(embedded: Option<&'static str>) -> String {
+    source(
+        std::env::args().skip(1),
+        io::stdin().is_terminal(),
+        embedded,
+    )
+    .and_then(Source::read)
+    .unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(2)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_source() {
+        assert_eq!(
+            source(args(&["in.txt"]), true, None).unwrap(),
+            Source::File("in.txt".into())
+        );
+        assert_eq!(
+            source(args(&["--input", "in.txt"]), false, Some("x")).unwrap(),
+            Source::File("in.txt".into())
+        );
+        assert_eq!(source(args(&["-"]), true, None).unwrap(), Source::Stdin);
+        assert_eq!(
+            source(args(&[]), false, Some("x")).unwrap(),
+            Source::Stdin
+        );
+        assert_eq!(
+            source(args(&[]), true, Some("x")).unwrap(),
+            Source::Embedded("x")
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert!(matches!(normalize("a b\n\nc\n"), Cow::Borrowed(_)));
+        assert_eq!(normalize("a b\r\n\r\nc\r\n"), "a b\n\nc\n");
+        assert_eq!(normalize("a b  \n \t\nc\t\n\n \n"), "a b\n\nc\n");
+        assert_eq!(normalize("a b\nc"), "a b\nc\n");
+        assert_eq!(normalize("  a\n"), "  a\n");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn test_source_errors() {
+        assert!(matches!(
+            source(args(&[]), true, None),
+            Err(InputError::Missing)
+        ));
+        assert!(matches!(
+            source(args(&["--input"]), true, None),
+            Err(InputError::Usage(_))
+        ));
+        assert!(matches!(
+            source(args(&["--verbose"]), true, None),
+            Err(InputError::Usage(_))
+        ));
+        assert!(matches!(
+            source(args(&["a.txt", "b.txt"]), true, None),
+            Err(InputError::Usage(_))
+        ));
+        assert!(matches!(
+            Source::File("does/not/exist.txt".into()).read(),
+            Err(InputError::Io(..))
+        ));
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt::{Display, Formatter};
+
+pub mod error;
+pub mod input;
+pub mod replay;
+pub mod snapshot;
+pub mod trace;
+
+use error::{ParseError, SolveError};
+use input::normalize;
+
+/// A puzzle day: parse the input once, then answer both parts from it.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input<'a>;
+
+    /// Parses `input` as `normalize` leaves it, the only form supported:
+    /// `solve` and the day binaries normalize before parsing, and anything
+    /// else calling `parse` or a day's own functions must too.
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+
+    /// Answers part one, failing with `SolveError::NoAnswer` when the input
+    /// parses but has no answer, such as a path that never arrives.
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError>;
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError>;
+
+    /// Parses the normalized `input` and answers `part`.
+    fn solve(input: &str, part: Part) -> Result<Answer, SolveError> {
+        let input = normalize(input);
+        let input = Self::parse(&input)?;
+        match part {
+            Part::Part1 => Self::part1(&input),
+            Part::Part2 => Self::part2(&input),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    Part1,
+    Part2,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::Part1 => f.pad("part1"),
+            Part::Part2 => f.pad("part2"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => Display::fmt(n, f),
+            Answer::Text(s) => f.pad(s),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Number(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_number!(u32, u64, usize, i32, i64);
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        Answer::Number(i128::try_from(value).expect("should fit in an i128"))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lines;
+
+    impl Solution for Lines {
+        const DAY: u8 = 0;
+        type Input<'a> = Vec<&'a str>;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+            input
+                .lines()
+                .map(|line| match line {
+                    "" => Err(ParseError::at_slice(input, line, "empty line")),
+                    line => Ok(line),
+                })
+                .collect()
+        }
+
+        fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+            Ok(input.len().into())
+        }
+
+        fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+            input
+                .last()
+                .map(|&line| line.into())
+                .ok_or_else(|| SolveError::no_answer("no last line"))
+        }
+    }
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(Answer::from(42u32), Answer::from(42usize));
+        assert_eq!(Answer::from(-3i32).to_string(), "-3");
+        assert_eq!(Answer::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(Lines::solve("a\nb\nc", Part::Part1), Ok(3u32.into()));
+        assert_eq!(Lines::solve("a\nb\nc", Part::Part2), Ok("c".into()));
+        let Err(SolveError::Malformed(err)) =
+            Lines::solve("a\n\nc", Part::Part1)
+        else {
+            panic!("should be malformed");
+        };
+        assert_eq!((err.line(), err.column()), (2, 1));
+        assert_eq!(
+            Lines::solve("", Part::Part2),
+            Err(SolveError::no_answer("no last line"))
+        );
+    }
+}
@@ -0,0 +1,18 @@
+/// A simulation recorded step by step, such as the waves of a search or
+/// the tilts of a platform, so a viewer can show any step of it.
+pub trait Replay {
+    /// How many frames there are, the starting state being the first.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The state shown in `frame`, one line per row.
+    fn render(&self, frame: usize) -> String;
+
+    /// What happened in `frame`, for the viewer's title.
+    fn caption(&self, frame: usize) -> String {
+        format!("step {}", frame)
+    }
+}
@@ -0,0 +1,41 @@
+/// A grid state to show in the browser: its tiles as text, with layers of
+/// cells to highlight, such as the pipe loop or the energized tiles.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snapshot {
+    pub caption: String,
+    pub rows: Vec<String>,
+    pub layers: Vec<Layer>,
+}
+
+/// Cells highlighted together, as `(m, n)` with `m` the row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+    pub name: String,
+    pub cells: Vec<(usize, usize)>,
+}
+
+impl Snapshot {
+    pub fn new(caption: impl Into<String>, text: &str) -> Snapshot {
+        Snapshot {
+            caption: caption.into(),
+            rows: text.lines().map(str::to_string).collect(),
+            layers: vec![],
+        }
+    }
+
+    /// Adds a layer over `cells`, which are sorted so states compare and
+    /// serialize the same whatever order the solver found them in.
+    pub fn with_layer(
+        mut self,
+        name: impl Into<String>,
+        cells: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Snapshot {
+        let mut cells: Vec<(usize, usize)> = cells.into_iter().collect();
+        cells.sort_unstable();
+        self.layers.push(Layer {
+            name: name.into(),
+            cells,
+        });
+        self
+    }
+}
@@ -0,0 +1,18 @@
+use std::env;
+
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+
+/// Logs the solvers' spans and events to stderr when `RUST_LOG` is set,
+/// e.g. `RUST_LOG=day14=debug`. Without it no subscriber is installed and
+/// the instrumentation is skipped at each callsite.
+pub fn init() {
+    if env::var_os(EnvFilter::DEFAULT_ENV).is_none() {
+        return;
+    }
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(std::io::stderr)
+        .init();
+}
@@ -0,0 +1,234 @@
+//! Cycle detection for iterated state machines. Applying a `step` over and
+//! over to a state from a finite set eventually brings back an earlier
+//! state, after which the same states repeat forever.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where the states start repeating, counted in steps from the initial
+/// state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first step whose state comes back.
+    pub start: usize,
+    /// The steps until it does.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as step `n`.
+    pub fn earliest(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Floyd's tortoise and hare: holds two states at a time and takes about
+/// three steps per state visited.
+pub fn floyd<S: PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let next = step(&hare);
+        hare = step(&next);
+    }
+
+    let mut start = 0;
+    let mut tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Brent's algorithm: holds two states at a time like Floyd's but takes
+/// fewer steps, finding the length before the start.
+pub fn brent<S: PartialEq + Clone>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+) -> Cycle {
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Every state up to the first repeat, so the state at any step can be
+/// looked up without stepping again.
+#[derive(Clone, Debug)]
+pub struct Orbit<S> {
+    states: Vec<S>,
+    cycle: Cycle,
+}
+
+impl<S: Hash + Eq + Clone> Orbit<S> {
+    /// Steps from `initial` until a state comes back, remembering each one.
+    pub fn new(initial: S, mut step: impl FnMut(&S) -> S) -> Orbit<S> {
+        let mut seen = HashMap::new();
+        let mut states = vec![];
+        let mut state = initial;
+        loop {
+            if let Some(&start) = seen.get(&state) {
+                let length = states.len() - start;
+                return Orbit {
+                    states,
+                    cycle: Cycle { start, length },
+                };
+            }
+            let next = step(&state);
+            seen.insert(state.clone(), states.len());
+            states.push(state);
+            state = next;
+        }
+    }
+}
+
+impl<S> Orbit<S> {
+    pub fn cycle(&self) -> Cycle {
+        self.cycle
+    }
+
+    /// The distinct states in step order, the initial one first; stepping
+    /// the last one gives the state at `cycle().start` again.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    /// The state after `n` steps.
+    pub fn at(&self, n: usize) -> &S {
+        &self.states[self.cycle.earliest(n)]
+    }
+}
+
+/// The state after `n` steps from `initial`, however large `n` is.
+pub fn state_at<S: Hash + Eq + Clone>(
+    initial: S,
+    step: impl FnMut(&S) -> S,
+    n: usize,
+) -> S {
+    Orbit::new(initial, step).at(n).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `x² + c mod m`, the classic rho shaped sequence.
+    fn rho(c: u64, m: u64) -> impl Fn(&u64) -> u64 {
+        move |x| (x * x + c) % m
+    }
+
+    /// The cycle found by remembering every step in a list.
+    fn naive(initial: u64, step: impl Fn(&u64) -> u64) -> Cycle {
+        let mut seen = vec![initial];
+        loop {
+            let next = step(seen.last().unwrap());
+            if let Some(start) = seen.iter().position(|s| *s == next) {
+                let length = seen.len() - start;
+                return Cycle { start, length };
+            }
+            seen.push(next);
+        }
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        for m in 1..60 {
+            for c in 0..5 {
+                for initial in 0..m {
+                    let expected = naive(initial, rho(c, m));
+                    assert_eq!(floyd(initial, rho(c, m)), expected);
+                    assert_eq!(brent(initial, rho(c, m)), expected);
+                    assert_eq!(
+                        Orbit::new(initial, rho(c, m)).cycle(),
+                        expected
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cycle() {
+        // 3 -> 0 -> 1 -> 2 -> 5 -> 6 -> 7 -> 0
+        let cycle = Cycle {
+            start: 1,
+            length: 6,
+        };
+        assert_eq!(floyd(3, rho(1, 10)), cycle);
+        assert_eq!(cycle.earliest(0), 0);
+        assert_eq!(cycle.earliest(6), 6);
+        assert_eq!(cycle.earliest(7), 1);
+        assert_eq!(cycle.earliest(1_000_000_000), 4);
+        assert_eq!(
+            floyd(5, |_: &u64| 5),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_orbit() {
+        let orbit = Orbit::new(3, rho(1, 10));
+        assert_eq!(orbit.states(), [3, 0, 1, 2, 5, 6, 7]);
+        let mut state = 3;
+        for n in 0..50 {
+            assert_eq!(*orbit.at(n), state, "step {}", n);
+            state = rho(1, 10)(&state);
+        }
+        assert_eq!(*orbit.at(1_000_000_000), 5);
+        assert_eq!(state_at(3, rho(1, 10), 1_000_000_000), 5);
+
+        let words = Orbit::new("ab".to_string(), |s| {
+            let (first, rest) = s.split_at(1);
+            format!("{}{}", rest, first)
+        });
+        assert_eq!(
+            words.cycle(),
+            Cycle {
+                start: 0,
+                length: 2
+            }
+        );
+        assert_eq!(words.at(3), "ba");
+    }
+}
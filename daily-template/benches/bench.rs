@@ -0,0 +1,36 @@
+use common::input::normalize;
+use common::Solution;
+use divan::{black_box, AllocProfiler, Bencher};
+use {{project-name}}::Day{{day}};
+
+const INPUT: &str = include_str!("../src/input.txt");
+
+// Report allocations and bytes per call next to each timing.
+#[global_allocator]
+static ALLOC: AllocProfiler = AllocProfiler::system();
+
+fn main() {
+    // Run registered benchmarks.
+    divan::main();
+}
+
+#[divan::bench]
+fn parse(bencher: Bencher) {
+    // The runner normalizes line endings and trailing blanks before parsing.
+    let input = normalize(INPUT);
+    bencher.bench(|| Day{{day}}::parse(black_box(&input)).unwrap());
+}
+
+#[divan::bench]
+fn part1(bencher: Bencher) {
+    let input = normalize(INPUT);
+    let input = Day{{day}}::parse(&input).unwrap();
+    bencher.bench(|| Day{{day}}::part1(black_box(&input)));
+}
+
+#[divan::bench]
+fn part2(bencher: Bencher) {
+    let input = normalize(INPUT);
+    let input = Day{{day}}::parse(&input).unwrap();
+    bencher.bench(|| Day{{day}}::part2(black_box(&input)));
+}
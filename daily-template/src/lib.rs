@@ -0,0 +1,62 @@
+use common::error::{ParseError, SolveError};
+use common::{Answer, Solution};
+
+/// `None` until the part is solved, which the runner shows as `unsolved`
+/// so `aoc run all` and `aoc calendar` work while the day is in progress.
+pub fn part1(input: &[&str]) -> Option<usize> {
+    let _ = input;
+    None
+}
+
+pub fn part2(input: &[&str]) -> Option<usize> {
+    let _ = input;
+    None
+}
+
+pub struct Day{{day}};
+
+impl Solution for Day{{day}} {
+    const DAY: u8 = {{day}};
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(input.lines().collect())
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(input).map_or("unsolved".into(), Answer::from))
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(input).map_or("unsolved".into(), Answer::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use rstest::{fixture, rstest};
+
+    use super::*;
+
+    #[fixture]
+    fn input() -> Vec<&'static str> {
+        Day{{day}}::parse(indoc! {
+            r#"
+            "#
+        })
+        .unwrap()
+    }
+
+    #[rstest]
+    #[ignore = "paste the example above and its answer here"]
+    fn test_part1(input: Vec<&str>) {
+        assert_eq!(part1(&input), Some(0));
+    }
+
+    #[rstest]
+    #[ignore = "paste the example above and its answer here"]
+    fn test_part2(input: Vec<&str>) {
+        assert_eq!(part2(&input), Some(0));
+    }
+}
@@ -1,32 +1,12 @@
+use common::embedded_input;
+use common::input::load_or_exit;
+use common::Solution;
+use {{project-name}}::{part1, part2, Day{{day}}};
+
 fn main() {
-    let input = include_str!("input.txt");
-    println!("{:?}", part1(input));
-    println!("{:?}", part2(input));
+    common::trace::init();
+    let input = load_or_exit(embedded_input!());
+    let input = Day{{day}}::parse(&input).unwrap_or_else(|err| err.exit());
+    println!("{:?}", part1(&input));
+    println!("{:?}", part2(&input));
 }
-
-fn part1(input: &str) {}
-
-
-fn part2(input: &str) {}
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use indoc::indoc;
-
-    #[test]
-    fn test_part1() {
-        let input = indoc! {
-            r#"
-            "#
-        };
-        // assert_eq!();
-    }
-
-
-    #[test]
-    fn test_part2() {
-        // assert_eq!();
-    }
-}
\ No newline at end of file
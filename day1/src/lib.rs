@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::iter::zip;
+
+use common::error::{ParseError, SolveError};
+use common::{Answer, Solution};
+
+/// Lines without a digit have no calibration value and are skipped.
+pub fn part1(input: &str) -> u32 {
+    input
+        .lines()
+        .map(|l| l.chars().filter_map(|c| c.to_digit(10)))
+        .filter_map(|mut it| {
+            let first = it.next()?;
+            let last = it.next_back().unwrap_or(first);
+            Some(first * 10 + last)
+        })
+        .sum()
+}
+
+pub fn part2(input: &str) -> u32 {
+    let words = vec![
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    let digits = vec!["1", "2", "3", "4", "5", "6", "7", "8", "9"];
+    let mappings =
+        zip(words.clone(), digits.clone()).collect::<HashMap<&str, &str>>();
+    let patterns = [&words[..], &digits[..]].concat();
+
+    input
+        .lines()
+        .map(|line| {
+            let mut v: Vec<u32> = vec![];
+            let mut index = 0;
+            while index < line.len() {
+                for p in patterns.iter() {
+                    if line.as_bytes()[index..].starts_with(p.as_bytes()) {
+                        let digit = mappings
+                            .get(p)
+                            .unwrap_or(p)
+                            .parse::<u32>()
+                            .unwrap();
+                        v.push(digit);
+                    }
+                }
+                index += 1;
+            }
+            v
+        })
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            [
+                v.first().unwrap().to_string(),
+                v.last().unwrap().to_string(),
+            ]
+            .join("")
+            .parse::<u32>()
+            .unwrap()
+        })
+        .sum()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part1(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(part2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE1: &str = include_str!("../examples/example1.txt");
+    const EXAMPLE2: &str = include_str!("../examples/example2.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(142, part1(EXAMPLE1));
+        assert_eq!(0, part1("abc\n"))
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(281, part2(EXAMPLE2));
+        assert_eq!(33, part2("é3\nxyz\n"))
+    }
+}
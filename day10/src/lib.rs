@@ -0,0 +1,374 @@
+use std::collections::{HashMap, HashSet};
+
+use common::error::{check_grid, ParseError, SolveError};
+use common::replay::Replay;
+use common::snapshot::Snapshot;
+use common::{Answer, Solution};
+use grid::{Coord, Direction, Grid};
+use picture::{Picture, Rgb, Style};
+use search::bfs;
+use tracing::{debug, trace};
+
+/// The sketch with the distance from the start written over each loop tile
+/// less than 10 steps away.
+fn render_distances(maze: &Maze, distance: &HashMap<Coord, usize>) -> String {
+    maze.grid
+        .map(|coord, &form| {
+            distance
+                .get(&coord)
+                .and_then(|d| char::from_digit(*d as u32, 10))
+                .unwrap_or(form)
+        })
+        .to_string()
+}
+
+/// Checks that the sketch only has pipe tiles and exactly one start.
+pub fn parse(input: &str) -> Result<&str, ParseError> {
+    check_grid(input, input.lines(), "|-LJ7F.S")?;
+    let mut starts = input.match_indices('S').map(|(i, _)| i);
+    if starts.next().is_none() {
+        return Err(ParseError::at(input, 0, "expected a start tile S"));
+    }
+    if let Some(i) = starts.next() {
+        return Err(ParseError::at(input, i, "second start tile"));
+    }
+    Ok(input)
+}
+
+pub fn part1(input: &str) -> Result<usize, SolveError> {
+    farthest(parse(input)?)
+}
+
+/// The maze of the sketch, failing when its start does not join two pipes
+/// and so is on no loop.
+fn maze(input: &str) -> Result<Maze, SolveError> {
+    let maze = Maze::new(input);
+    if maze.neighbours(maze.start).len() != 2 {
+        return Err(SolveError::no_answer("the start tile is not on a loop"));
+    }
+    Ok(maze)
+}
+
+fn farthest(input: &str) -> Result<usize, SolveError> {
+    let maze = maze(input)?;
+    let distance = loop_distances(&maze);
+    debug!("distances\n{}", render_distances(&maze, &distance));
+    Ok(*distance.values().max().unwrap())
+}
+
+/// Walks the loop from the start one wave at a time, both ways round, and
+/// returns how many steps away each of its tiles is.
+fn loop_distances(maze: &Maze) -> HashMap<Coord, usize> {
+    bfs(maze.start, |&coord| {
+        let neighbours = maze.neighbours(coord);
+        trace!(%coord, ?neighbours);
+        neighbours
+    })
+    .into_distances()
+}
+
+fn ray_check_inside(coord: Coord, seen: &HashSet<Coord>, maze: &Maze) -> bool {
+    // ray check from 0 to the coord
+    let count = (0..coord.n)
+        .map(|n| Coord::new(coord.m, n))
+        .filter(|c| seen.contains(c) && "|LJS".contains(maze.grid[*c]))
+        .count();
+    count % 2 != 0
+}
+
+pub fn part2(input: &str) -> Result<usize, SolveError> {
+    enclosed(parse(input)?)
+}
+
+fn enclosed(input: &str) -> Result<usize, SolveError> {
+    let maze = maze(input)?;
+    let seen: HashSet<Coord> = loop_distances(&maze).into_keys().collect();
+    Ok(enclosed_tiles(&maze, &seen).len())
+}
+
+/// The tiles inside the loop `seen`, found by casting a ray from each.
+fn enclosed_tiles(maze: &Maze, seen: &HashSet<Coord>) -> Vec<Coord> {
+    let mut row_min_max_map: HashMap<usize, (usize, usize)> = HashMap::new();
+    for coord in seen {
+        row_min_max_map
+            .entry(coord.m)
+            .and_modify(|(min, max)| {
+                *min = (*min).min(coord.n);
+                *max = (*max).max(coord.n);
+            })
+            .or_insert((coord.n, coord.n));
+    }
+
+    maze.grid
+        .coords()
+        .filter(|&coord| {
+            let (min, max) =
+                row_min_max_map.get(&coord.m).copied().unwrap_or((0, 0));
+            coord.n < max
+                && coord.n > min
+                && !seen.contains(&coord)
+                && ray_check_inside(coord, seen, maze)
+        })
+        .collect()
+}
+
+/// The loop, then the tiles it encloses, for the visualization server.
+pub fn snapshots(input: &str) -> Result<Vec<Snapshot>, ParseError> {
+    let maze = Maze::new(parse(input)?);
+    let seen: HashSet<Coord> = loop_distances(&maze).into_keys().collect();
+    let enclosed = enclosed_tiles(&maze, &seen);
+    let the_loop = Snapshot::new("the loop", input)
+        .with_layer("loop", cells(&seen))
+        .with_layer("start", cells(&[maze.start]));
+    let inside =
+        Snapshot::new(format!("enclosed tiles: {}", enclosed.len()), input)
+            .with_layer("loop", cells(&seen))
+            .with_layer("enclosed", cells(&enclosed));
+    Ok(vec![the_loop, inside])
+}
+
+/// `coords` as the `(m, n)` cells of a snapshot layer.
+fn cells<'a>(
+    coords: impl IntoIterator<Item = &'a Coord> + 'a,
+) -> impl Iterator<Item = (usize, usize)> + 'a {
+    coords.into_iter().map(|c| (c.m, c.n))
+}
+
+/// The loop, the tiles it encloses and the start, drawn as an image.
+pub struct LoopPicture {
+    maze: Maze,
+    the_loop: HashSet<Coord>,
+    enclosed: HashSet<Coord>,
+}
+
+pub fn picture(input: &str) -> Result<LoopPicture, ParseError> {
+    let maze = Maze::new(parse(input)?);
+    let the_loop: HashSet<Coord> = loop_distances(&maze).into_keys().collect();
+    let enclosed = enclosed_tiles(&maze, &the_loop).into_iter().collect();
+    Ok(LoopPicture {
+        maze,
+        the_loop,
+        enclosed,
+    })
+}
+
+const LOOP: Rgb = Rgb(255, 170, 0);
+const ENCLOSED: Rgb = Rgb(60, 180, 75);
+const OUTSIDE: Rgb = Rgb(40, 40, 40);
+const START: Rgb = Rgb(230, 25, 75);
+
+impl Picture for LoopPicture {
+    fn size(&self) -> (usize, usize) {
+        (self.maze.grid.height(), self.maze.grid.width())
+    }
+
+    fn style(&self, m: usize, n: usize) -> Style {
+        let coord = Coord::new(m, n);
+        let form = self.maze.grid[coord];
+        if coord == self.maze.start {
+            Style::fill(LOOP).with_glyph('S').with_overlay(START)
+        } else if self.the_loop.contains(&coord) {
+            Style::fill(LOOP).with_glyph(box_pipe(form))
+        } else if self.enclosed.contains(&coord) {
+            Style::fill(ENCLOSED)
+        } else if form != '.' {
+            Style::fill(OUTSIDE).with_glyph(box_pipe(form))
+        } else {
+            Style::fill(OUTSIDE)
+        }
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "a loop of {} tiles enclosing {}",
+            self.the_loop.len(),
+            self.enclosed.len()
+        )
+    }
+}
+
+/// The pipe as a box drawing character, which joins up with its neighbours.
+fn box_pipe(form: char) -> char {
+    match form {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        form => form,
+    }
+}
+
+/// The directions a tile's pipe leads, every way for the start tile.
+fn connections(form: char) -> &'static [Direction] {
+    use Direction::{East, North, South, West};
+    match form {
+        '|' => &[North, South],
+        '-' => &[East, West],
+        'L' => &[North, East],
+        'J' => &[North, West],
+        '7' => &[South, West],
+        'F' => &[South, East],
+        'S' => &Direction::ALL,
+        _ => &[],
+    }
+}
+
+struct Maze {
+    grid: Grid<char>,
+    start: Coord,
+}
+
+impl Maze {
+    fn new(input: &str) -> Maze {
+        let grid: Grid<char> =
+            input.parse().expect("should be a rectangular sketch");
+        let start = grid
+            .position(|&form| form == 'S')
+            .expect("should have a start tile");
+        Maze { grid, start }
+    }
+
+    /// The pipes connected to the one at `coord`: those it leads to that
+    /// lead back.
+    fn neighbours(&self, coord: Coord) -> Vec<Coord> {
+        connections(self.grid[coord])
+            .iter()
+            .filter_map(|&direction| {
+                let next = coord.step(direction)?;
+                let back = direction.opposite();
+                connections(*self.grid.get(next)?)
+                    .contains(&back)
+                    .then_some(next)
+            })
+            .collect()
+    }
+}
+
+/// The search along the loop, replayed one wave at a time: the tiles
+/// reached so far are drawn as box pipes.
+pub struct Waves {
+    maze: Maze,
+    distance: HashMap<Coord, usize>,
+}
+
+pub fn replay(input: &str) -> Result<Waves, ParseError> {
+    let maze = Maze::new(parse(input)?);
+    let distance = loop_distances(&maze);
+    Ok(Waves { maze, distance })
+}
+
+impl Replay for Waves {
+    fn len(&self) -> usize {
+        *self.distance.values().max().unwrap() + 1
+    }
+
+    fn render(&self, frame: usize) -> String {
+        self.maze
+            .grid
+            .map(|coord, &form| match self.distance.get(&coord) {
+                Some(&d) if d <= frame => box_pipe(form),
+                _ => form,
+            })
+            .to_string()
+    }
+
+    fn caption(&self, frame: usize) -> String {
+        format!("{} steps from the start", frame)
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        farthest(input).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        enclosed(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE1: &str = include_str!("../examples/example1.txt");
+    const EXAMPLE2: &str = include_str!("../examples/example2.txt");
+    const EXAMPLE3: &str = include_str!("../examples/example3.txt");
+    const EXAMPLE4: &str = include_str!("../examples/example4.txt");
+    const EXAMPLE5: &str = include_str!("../examples/example5.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE1), Ok(4));
+        assert_eq!(part1(EXAMPLE2), Ok(8));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE3), Ok(4));
+        assert_eq!(part2(EXAMPLE4), Ok(8));
+        assert_eq!(part2(EXAMPLE5), Ok(10));
+    }
+
+    #[test]
+    fn test_replay() {
+        let waves = replay(".....\n.S-7.\n.|.|.\n.L-J.\n.....\n").unwrap();
+        assert_eq!(waves.len(), 5);
+        assert_eq!(waves.render(1), ".....\n.S─7.\n.│.|.\n.L-J.\n.....\n");
+        assert_eq!(waves.render(4), ".....\n.S─┐.\n.│.│.\n.└─┘.\n.....\n");
+    }
+
+    #[test]
+    fn test_snapshots() {
+        let input = "F-7.\n|.|.\nS-J.\n";
+        let snapshots = snapshots(input).unwrap();
+        assert_eq!(snapshots[0].rows, ["F-7.", "|.|.", "S-J."]);
+        assert_eq!(snapshots[0].layers[0].cells.len(), 8);
+        assert_eq!(snapshots[0].layers[1].cells, [(2, 0)]);
+        assert_eq!(snapshots[1].caption, "enclosed tiles: 1");
+        assert_eq!(snapshots[1].layers[1].cells, [(1, 1)]);
+    }
+
+    #[test]
+    fn test_picture() {
+        let picture = picture("F-7.\n|.|.\nS-J.\n").unwrap();
+        assert_eq!(picture.size(), (3, 4));
+        assert_eq!(picture.title(), "a loop of 8 tiles enclosing 1");
+        assert_eq!(
+            picture.style(2, 0),
+            Style::fill(LOOP).with_glyph('S').with_overlay(START)
+        );
+        assert_eq!(picture.style(0, 0), Style::fill(LOOP).with_glyph('┌'));
+        assert_eq!(picture.style(1, 1), Style::fill(ENCLOSED));
+        assert_eq!(picture.style(0, 3), Style::fill(OUTSIDE));
+        let svg = picture.to_svg(8);
+        assert_eq!(svg.matches("fill=\"#ffaa00\"").count(), 8);
+    }
+
+    #[test]
+    fn test_malformed_sketch() {
+        let err = parse("S-7\n|.X\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 3));
+        let err = parse("F-7\nL-J\n").unwrap_err();
+        assert_eq!(err.message(), "expected a start tile S");
+        let err = parse("S-7\n|.|\nS-J\n").unwrap_err();
+        assert_eq!((err.line(), err.message()), (3, "second start tile"));
+        let err = parse("S-7\n|\nL-J\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 2));
+        let no_answer =
+            SolveError::no_answer("the start tile is not on a loop");
+        assert_eq!(part1("S\n"), Err(no_answer.clone()));
+        assert_eq!(part2("S-\n..\n"), Err(no_answer));
+    }
+}
@@ -1,33 +1,55 @@
-use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::hash::Hash;
 
+use common::error::{check_grid, ParseError, SolveError};
+use common::snapshot::Snapshot;
+use common::{Answer, Solution};
+use grid::{Coord, Grid};
 use itertools::Itertools;
-use pathfinding::prelude::astar;
+use search::astar;
+use tracing::debug;
+
+/// Checks that the image is a rectangle of galaxies and empty space.
+pub fn parse(input: &str) -> Result<&str, ParseError> {
+    check_grid(input, input.lines(), ".#").map(|_| input)
+}
+
+/// The image with its empty rows and columns, then the universe expanded,
+/// for the visualization server.
+pub fn snapshots(input: &str) -> Result<Vec<Snapshot>, ParseError> {
+    let mut universe = Universe::new(parse(input)?);
+    let (rows_indexes, cols_indexes) = universe.get_empty_indexes();
+    let empty = universe.grid.coords().filter(|c| {
+        rows_indexes.contains(&c.m) || cols_indexes.contains(&c.n)
+    });
+    let image = Snapshot::new("the image", &universe.to_string())
+        .with_layer("galaxies", universe.galaxies())
+        .with_layer("empty", empty.map(|c| (c.m, c.n)));
+    let expanded = universe.expand();
+    let expanded = Snapshot::new("expanded universe", &expanded.to_string())
+        .with_layer("galaxies", expanded.galaxies());
+    Ok(vec![image, expanded])
+}
 
 pub fn process(input: &str, expand_size: usize) -> usize {
     let universe = Universe::new(input);
-    // println!("{}", universe);
-    let edges = universe.edges(expand_size);
+    debug!("universe\n{}", universe);
+    let empty = universe.get_empty_indexes();
     universe
         .get_pairs()
-        .map(|pair| universe.a_star_distance(pair, &edges))
+        .map(|pair| universe.a_star_distance(pair, &empty, expand_size))
         .sum()
 }
 
 pub fn process_manhattan(input: &str, expand_size: usize) -> usize {
     let universe = Universe::new(input);
-    // println!("{}", universe);
-    let edges = universe.edges(expand_size);
+    debug!("universe\n{}", universe);
     universe
         .get_pairs()
         .map(|pair| universe.manhattan_distance(pair, expand_size))
         .sum()
 }
 
-type Coord = (usize, usize);
-type Edge = (Coord, Coord);
-
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
 struct Galaxy {
     m: usize,
@@ -37,131 +59,86 @@ struct Galaxy {
 
 impl Galaxy {
     fn coord(&self) -> Coord {
-        (self.m, self.n)
+        Coord::new(self.m, self.n)
     }
 }
 
 struct Universe {
-    grid: Vec<Vec<char>>,
-    height: usize,
-    width: usize,
+    grid: Grid<char>,
 }
 
 impl Universe {
     fn new(input: &str) -> Universe {
-        let grid: Vec<Vec<char>> =
-            input.lines().map(|line| line.chars().collect()).collect();
-        let height = grid.len();
-        let width = grid[0].len();
         Universe {
-            grid,
-            height,
-            width,
-        }
-    }
-
-    fn edges(&self, with_expand_size: usize) -> HashMap<Edge, usize> {
-        let (rows_indexes, cols_indexes) = self.get_empty_indexes();
-        let start = Coord::default();
-        let mut stack = vec![start];
-        let mut edges = HashMap::new();
-        let mut visited = HashSet::new();
-
-        while !stack.is_empty() {
-            let mut new_stack = vec![];
-            while let Some(node) = stack.pop() {
-                for neighbour in self.get_neighbours(node) {
-                    if visited.contains(&(node, neighbour))
-                        || visited.contains(&(neighbour, node))
-                    {
-                        continue;
-                    }
-                    visited.insert((node, neighbour));
-                    visited.insert((neighbour, node));
-
-                    let mut distance = 1;
-                    if rows_indexes.contains(&(neighbour.0))
-                        || cols_indexes.contains(&(neighbour.1))
-                    {
-                        distance = with_expand_size;
-                    }
-                    edges.insert((node, neighbour), distance);
-                    edges.insert((neighbour, node), distance);
-
-                    new_stack.push(neighbour);
-                }
-            }
-            stack = new_stack;
+            grid: input.parse().expect("should be a rectangular image"),
         }
-        edges
     }
 
     /// Get the row and cols indexes where lines only have '.' char.
     fn get_empty_indexes(&self) -> (Vec<usize>, Vec<usize>) {
-        let mut rows_indexes: Vec<usize> = vec![];
-        let mut cols_indexes: Vec<usize> = vec![];
-
-        for m in 0..self.height {
-            if self.grid[m].iter().all(|c| *c == '.') {
-                rows_indexes.push(m);
-            }
-        }
-        for n in 0..self.width {
-            if (0..self.height).map(|m| self.grid[m][n]).all(|c| c == '.') {
-                cols_indexes.push(n);
-            }
-        }
+        let rows_indexes = self
+            .grid
+            .rows()
+            .positions(|row| row.iter().all(|c| *c == '.'))
+            .collect();
+        let cols_indexes = self
+            .grid
+            .columns()
+            .positions(|mut col| col.all(|c| *c == '.'))
+            .collect();
 
         (rows_indexes, cols_indexes)
     }
 
     fn expand(&mut self) -> Universe {
         let (rows_indexes, cols_indexes) = self.get_empty_indexes();
-        rows_indexes.into_iter().enumerate().for_each(|(i, m)| {
-            self.grid.insert(m + i, self.grid[m + i].clone())
-        });
-
-        let height = self.grid.len();
-        cols_indexes.into_iter().enumerate().for_each(|(i, n)| {
-            (0..height).for_each(|m| self.grid[m].insert(n + i, '.'))
-        });
-        let width = self.grid[0].len();
+        let rows = self
+            .grid
+            .rows()
+            .enumerate()
+            .flat_map(|(m, row)| {
+                let row: Vec<char> = row
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(n, ch)| {
+                        let copies =
+                            if cols_indexes.contains(&n) { 2 } else { 1 };
+                        vec![*ch; copies]
+                    })
+                    .collect();
+                let copies = if rows_indexes.contains(&m) { 2 } else { 1 };
+                vec![row; copies]
+            })
+            .collect();
+        self.grid = Grid::from_rows(rows).expect("should stay rectangular");
 
         Universe {
             grid: self.grid.clone(),
-            width,
-            height,
         }
     }
 
+    fn galaxies(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.grid
+            .iter()
+            .filter(|(_, ch)| **ch == '#')
+            .map(|(c, _)| (c.m, c.n))
+    }
+
     fn get_pairs(&self) -> impl Iterator<Item = (Galaxy, Galaxy)> + '_ {
         self.grid
             .iter()
+            .filter(|(_, ch)| **ch == '#')
             .enumerate()
-            .flat_map(|(m, row)| {
-                row.iter()
-                    .enumerate()
-                    .filter(|(_, ch)| **ch == '#')
-                    .map(move |(n, _)| (m, n))
+            .map(|(id, (c, _))| Galaxy {
+                m: c.m,
+                n: c.n,
+                id: id + 1,
             })
-            .enumerate()
-            .map(|(id, (m, n))| Galaxy { m, n, id: id + 1 })
             .tuple_combinations()
     }
 
     fn get_neighbours(&self, node: Coord) -> Vec<Coord> {
-        [(0, 1), (1, 0), (0, -1), (-1, 0)]
-            .into_iter()
-            .map(|(dx, dy)| {
-                (
-                    node.0.saturating_add_signed(dx),
-                    node.1.saturating_add_signed(dy),
-                )
-            })
-            .filter(|coord| {
-                *coord != node && coord.0 < self.height && coord.1 < self.width
-            })
-            .collect()
+        self.grid.neighbours4(node).collect()
     }
 
     fn manhattan_distance(
@@ -172,7 +149,7 @@ impl Universe {
         let (start, end) = (pair.0.coord(), pair.1.coord());
         let (rows_indexes, cols_indexes) = self.get_empty_indexes();
 
-        let a: usize = (start.0.min(end.0)..start.0.max(end.0))
+        let a: usize = (start.m.min(end.m)..start.m.max(end.m))
             .map(|r| {
                 if rows_indexes.contains(&r) {
                     return expand_size;
@@ -180,7 +157,7 @@ impl Universe {
                 1
             })
             .sum();
-        let b: usize = (start.1.min(end.1)..start.1.max(end.1))
+        let b: usize = (start.n.min(end.n)..start.n.max(end.n))
             .map(|c| {
                 if cols_indexes.contains(&c) {
                     return expand_size;
@@ -191,39 +168,64 @@ impl Universe {
         a + b
     }
 
+    /// The shortest walk between the pair, a step costing `expand_size`
+    /// when its end farther from the top left corner is on an empty row or
+    /// column.
     fn a_star_distance(
         &self,
         pair: (Galaxy, Galaxy),
-        edges: &HashMap<Edge, usize>,
+        (rows_indexes, cols_indexes): &(Vec<usize>, Vec<usize>),
+        expand_size: usize,
     ) -> usize {
         let (start, end) = (pair.0.coord(), pair.1.coord());
-
-        let heuristic = |node: &Coord| -> usize {
-            node.0.abs_diff(end.0) + node.1.abs_diff(end.1)
+        let step = |from: Coord, to: Coord| {
+            let far = if to.m + to.n > from.m + from.n {
+                to
+            } else {
+                from
+            };
+            let expanded =
+                rows_indexes.contains(&far.m) || cols_indexes.contains(&far.n);
+            (to, if expanded { expand_size } else { 1 })
         };
 
-        astar(
-            &start,
-            |n| {
-                self.get_neighbours(*n)
-                    .iter()
-                    .map(|neighbour| (*neighbour, edges[&(*neighbour, *n)]))
-                    .collect::<Vec<(Coord, usize)>>()
+        let (_, paths) = astar(
+            start,
+            |&n| {
+                self.get_neighbours(n)
+                    .into_iter()
+                    .map(move |neighbour| step(n, neighbour))
             },
-            heuristic,
+            |node| node.manhattan(end),
             |n| *n == end,
         )
-        .expect("should have a distance")
-        .1
+        .expect("should have a distance");
+        paths.distance(&end).expect("should reach the end")
     }
 }
 
 impl Display for Universe {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in self.grid.iter() {
-            writeln!(f, "{}", row.iter().collect::<String>())?;
-        }
-        Ok(())
+        write!(f, "{}", self.grid)
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(process_manhattan(input, 2).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(process_manhattan(input, 1000000).into())
     }
 }
 
@@ -234,27 +236,11 @@ mod tests {
 
     use super::*;
 
-    #[fixture]
-    fn input() -> &'static str {
-        return indoc! {
-            r"
-            ...#......
-            .......#..
-            #.........
-            ..........
-            ......#...
-            .#........
-            .........#
-            ..........
-            .......#..
-            #...#.....
-            "
-        };
-    }
+    const EXAMPLE: &str = include_str!("../examples/example1.txt");
 
     #[fixture]
-    fn universe(input: &str) -> Universe {
-        Universe::new(input)
+    fn universe() -> Universe {
+        Universe::new(EXAMPLE)
     }
 
     #[rstest]
@@ -282,25 +268,36 @@ mod tests {
         assert_eq!(universe.to_string(), expanded_uni);
     }
 
-    #[rstest]
-    fn test_part1(input: &str) {
-        let universe = Universe::new(input);
-        let edges = universe.edges(2);
+    #[test]
+    fn test_snapshots() {
+        let snapshots = snapshots(EXAMPLE).unwrap();
+        assert_eq!(snapshots[0].rows.len(), 10);
+        assert_eq!(snapshots[0].layers[0].cells.len(), 9);
+        assert_eq!(snapshots[0].layers[1].cells.len(), 2 * 10 + 3 * 10 - 6);
+        assert_eq!(snapshots[1].rows.len(), 12);
+        assert_eq!(snapshots[1].rows[0].len(), 13);
+        assert_eq!(snapshots[1].layers[0].cells[0], (0, 4));
+    }
+
+    #[test]
+    fn test_part1() {
+        let universe = Universe::new(EXAMPLE);
+        let empty = universe.get_empty_indexes();
         universe.get_pairs().for_each(|(g1, g2)| {
             if g1.id == 3 && g2.id == 6 {
-                assert_eq!(17, universe.a_star_distance((g1, g2), &edges));
+                assert_eq!(17, universe.a_star_distance((g1, g2), &empty, 2));
             }
         });
 
-        assert_eq!(374, process(input, 2));
-        assert_eq!(374, process_manhattan(input, 2));
+        assert_eq!(374, process(EXAMPLE, 2));
+        assert_eq!(374, process_manhattan(EXAMPLE, 2));
     }
 
-    #[rstest]
-    fn test_part2(input: &str) {
-        assert_eq!(process(input, 2), 374);
-        assert_eq!(process(input, 10), 1030);
-        assert_eq!(process(input, 100), 8410);
-        assert_eq!(8410, process_manhattan(input, 100));
+    #[test]
+    fn test_part2() {
+        assert_eq!(process(EXAMPLE, 2), 374);
+        assert_eq!(process(EXAMPLE, 10), 1030);
+        assert_eq!(process(EXAMPLE, 100), 8410);
+        assert_eq!(8410, process_manhattan(EXAMPLE, 100));
     }
 }
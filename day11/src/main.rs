@@ -1,7 +1,10 @@
-use day11::{process, process_manhattan};
+use common::embedded_input;
+use common::input::load_or_exit;
+use day11::process_manhattan;
 
 fn main() {
-    let input = include_str!("input.txt");
+    common::trace::init();
+    let input = &load_or_exit(embedded_input!());
     // println!("{}", process(input, 2));
     // println!("{}", process(input, 1000000));
     println!("{}", process_manhattan(input, 2));
@@ -0,0 +1,195 @@
+use cached::proc_macro::cached;
+use common::error::{ParseError, SolveError};
+use common::{Answer, Solution};
+use std::iter::repeat_n;
+
+/// Checks every row's springs and group sizes, and that even unfolded the
+/// rows can't have more arrangements than fit in a `usize`.
+pub fn parse(input: &str) -> Result<&str, ParseError> {
+    let mut total = 0usize;
+    for line in input.lines() {
+        let (springs, numbers) = line.split_once(' ').ok_or_else(|| {
+            ParseError::at_slice(input, &line[line.len()..], "expected groups")
+        })?;
+        if let Some(i) = springs.find(|c| !".#?".contains(c)) {
+            return Err(ParseError::at_slice(
+                input,
+                &springs[i..],
+                "expected a spring",
+            ));
+        }
+        let numbers = numbers
+            .split(',')
+            .map(|n| match n.parse::<usize>() {
+                Ok(size) if (1..=springs.len()).contains(&size) => Ok(size),
+                _ => Err(ParseError::at_slice(
+                    input,
+                    n,
+                    "expected a group size",
+                )),
+            })
+            .collect::<Result<Vec<usize>, ParseError>>()?;
+        total = max_arrangements(springs.len(), &numbers)
+            .and_then(|n| total.checked_add(n))
+            .ok_or_else(|| {
+                ParseError::at_slice(input, line, "too many arrangements")
+            })?;
+    }
+    Ok(input)
+}
+
+/// An upper bound on a row's arrangements once unfolded: the ways to place
+/// its groups, each followed by a gap, in the springs.
+fn max_arrangements(springs: usize, numbers: &[usize]) -> Option<usize> {
+    let (length, groups) = (springs * 5 + 4, numbers.len() * 5);
+    let damaged = numbers.iter().sum::<usize>() * 5;
+    let slack = (length + 1).saturating_sub(damaged);
+    if groups > slack {
+        return Some(0);
+    }
+    let k = groups.min(slack - groups);
+    (0..k)
+        .try_fold(1u128, |acc, i| {
+            Some(acc.checked_mul((slack - i) as u128)? / (i as u128 + 1))
+        })
+        .and_then(|n| usize::try_from(n).ok())
+}
+
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    parse(input).map(sum_arrangements)
+}
+
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    parse(input).map(sum_arrangements_with_repeat)
+}
+
+fn sum_arrangements(input: &str) -> usize {
+    input.lines().map(find_arrangements).sum()
+}
+
+fn sum_arrangements_with_repeat(input: &str) -> usize {
+    input.lines().map(find_arrangement_with_repeat).sum()
+}
+
+fn find_arrangement_with_repeat(line: &str) -> usize {
+    let (springs, numbers) = line.split_once(' ').unwrap();
+    let (springs, numbers) = (
+        repeat_n(springs, 5).collect::<Vec<&str>>().join("?"),
+        repeat_n(numbers, 5).collect::<Vec<&str>>().join(","),
+    );
+    let (springs, numbers) = (
+        springs.chars().collect(),
+        numbers
+            .split(',')
+            .map(|s| s.parse::<usize>().unwrap())
+            .collect::<Vec<usize>>(),
+    );
+    cached_count(springs, numbers)
+}
+
+#[cached]
+fn cached_count(springs: Vec<char>, numbers: Vec<usize>) -> usize {
+    if numbers.is_empty() {
+        return if springs.contains(&'#') { 0 } else { 1 };
+    }
+    if springs.is_empty() {
+        return if numbers.is_empty() { 1 } else { 0 };
+    }
+
+    let mut res = 0;
+    if ".?".contains(springs[0]) {
+        res += cached_count(
+            springs.get(1..).unwrap_or_default().to_vec(),
+            numbers.clone(),
+        )
+    }
+    if "#?".contains(springs[0])
+        && numbers[0] <= springs.len()
+        && !springs[..numbers[0]].contains(&'.')
+        && (springs.len() == numbers[0] || springs[numbers[0]] != '#')
+    {
+        res += cached_count(
+            springs.get(numbers[0] + 1..).unwrap_or_default().to_vec(),
+            numbers.get(1..).unwrap_or_default().to_vec().to_vec(),
+        )
+    }
+    res
+}
+
+fn find_arrangements(line: &str) -> usize {
+    let (springs, numbers) = line.split_once(' ').unwrap();
+    let numbers = numbers
+        .split(',')
+        .map(|s| s.parse::<usize>().unwrap())
+        .collect::<Vec<usize>>();
+    let springs = springs.chars().collect();
+
+    cached_count(springs, numbers)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(sum_arrangements(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(sum_arrangements_with_repeat(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/example1.txt");
+
+    #[test]
+    fn test_example() {
+        assert_eq!(part1(EXAMPLE), Ok(21));
+        assert_eq!(part2(EXAMPLE), Ok(525152));
+    }
+
+    #[rstest]
+    #[case("???.### 1,1,3", 1)]
+    #[case(".??..??...?##. 1,1,3", 4)]
+    #[case("?#?#?#?#?#?#?#? 1,3,1,6", 1)]
+    #[case("????.#...#... 4,1,1", 1)]
+    #[case("????.######..#####. 1,6,5", 4)]
+    #[case("?###???????? 3,2,1", 10)]
+    fn test_part1(#[case] line: &str, #[case] expected: usize) {
+        assert_eq!(find_arrangements(line), expected);
+    }
+
+    #[rstest]
+    #[case("???.### 1,1,3", 1)]
+    #[case(".??..??...?##. 1,1,3", 16384)]
+    #[case("?#?#?#?#?#?#?#? 1,3,1,6", 1)]
+    #[case("????.#...#... 4,1,1", 16)]
+    #[case("????.######..#####. 1,6,5", 2500)]
+    #[case("?###???????? 3,2,1", 506250)]
+    fn test_part2(#[case] line: &str, #[case] expected: usize) {
+        assert_eq!(find_arrangement_with_repeat(line), expected);
+    }
+
+    #[test]
+    fn test_malformed_row() {
+        let err = part1("???.### 1,1,3\n??x 1\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 3));
+        let err = part1("???.### 1,,3\n").unwrap_err();
+        assert_eq!(err.message(), "expected a group size");
+        let line = format!("{} {}", "?".repeat(99), ["1"; 30].join(","));
+        let err = part2(&line).unwrap_err();
+        assert_eq!(err.message(), "too many arrangements");
+    }
+}
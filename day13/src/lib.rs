@@ -0,0 +1,119 @@
+use std::iter::zip;
+
+use common::error::{check_grid, ParseError, SolveError};
+use common::{Answer, Solution};
+
+pub fn process(input: &str, smudge: usize) -> Result<usize, ParseError> {
+    parse(input).map(|patterns| summarize(&patterns, smudge))
+}
+
+pub fn parse(input: &str) -> Result<Vec<Matrix>, ParseError> {
+    let lines: Vec<&str> = input.lines().map(str::trim_end).collect();
+    lines
+        .split(|line| line.is_empty())
+        .filter(|pattern| !pattern.is_empty())
+        .map(|pattern| {
+            check_grid(input, pattern.iter().copied(), ".#")?;
+            Ok(Matrix::new(pattern))
+        })
+        .collect()
+}
+
+pub fn summarize(patterns: &[Matrix], smudge: usize) -> usize {
+    patterns.iter().map(|m| m.find_mirror(smudge)).sum()
+}
+
+#[derive(Debug)]
+pub struct Matrix {
+    rows: Vec<Vec<u8>>,
+    cols: Vec<Vec<u8>>,
+}
+
+impl Matrix {
+    fn new(lines: &[&str]) -> Matrix {
+        let rows: Vec<Vec<u8>> =
+            lines.iter().map(|l| l.as_bytes().to_vec()).collect();
+        let height = rows.len();
+        let width = rows[0].len();
+
+        let cols: Vec<Vec<u8>> = (0..width)
+            .map(|n| (0..height).map(|m| rows[m][n]).collect())
+            .collect();
+
+        Matrix { rows, cols }
+    }
+
+    /// The pattern's summary, or 0 when it has no line of reflection.
+    fn find_mirror(&self, smudge_n: usize) -> usize {
+        let find_index = |lines: &Vec<Vec<u8>>| -> Option<usize> {
+            (0..lines.len() - 1)
+                .position(|index| {
+                    let diff = zip(
+                        lines[..index + 1].iter().rev(),
+                        lines[index + 1..].iter(),
+                    )
+                    .map(|(l1, l2)| {
+                        zip(l1, l2).filter(|(c1, c2)| c1 != c2).count()
+                    })
+                    .sum::<usize>();
+                    diff == smudge_n
+                })
+                .map(|index| index + 1)
+        };
+        find_index(&self.rows)
+            .map(|c| c * 100)
+            .or(find_index(&self.cols))
+            .unwrap_or(0)
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input<'a> = Vec<Matrix>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(summarize(input, 0).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(summarize(input, 1).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/example1.txt");
+
+    #[test]
+    fn test_process() {
+        let patterns = parse(EXAMPLE).unwrap();
+        assert_eq!(patterns[0].find_mirror(0), 5);
+        assert_eq!(patterns[0].find_mirror(1), 300);
+        assert_eq!(patterns[1].find_mirror(0), 400);
+        assert_eq!(patterns[1].find_mirror(1), 100);
+        assert_eq!(process(EXAMPLE, 0), Ok(405));
+    }
+
+    #[test]
+    fn test_parse_crlf() {
+        let input = "#.\r\n#.\r\n\r\n..\r\n##\r\n\r\n";
+        let patterns = parse(input).unwrap();
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(summarize(&patterns, 0), 101);
+    }
+
+    #[test]
+    fn test_malformed_pattern() {
+        let err = process("#.\n#.\n\n..\n#\n", 0).unwrap_err();
+        assert_eq!((err.line(), err.column()), (5, 2));
+        assert_eq!(process("#.\n.#\n", 0), Ok(0));
+    }
+}
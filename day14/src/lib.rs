@@ -1,92 +1,147 @@
-use std::collections::HashMap;
-use std::fmt::{Display, Formatter};
+use common::error::{check_grid, ParseError, SolveError};
+use common::replay::Replay;
+use common::{Answer, Solution};
+use cycles::{Cycle, Orbit};
+use grid::{Coord, Direction, Grid};
+use picture::{Picture, Rgb, Style};
+use tracing::{debug, trace};
 
-pub fn part1(input: &str) -> usize {
-    let mut matrix = Matrix::new(input);
+/// Checks that the platform is a rectangle of rocks and empty spaces.
+pub fn parse(input: &str) -> Result<&str, ParseError> {
+    check_grid(input, input.lines(), ".#O").map(|_| input)
+}
+
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    parse(input).map(north_load)
+}
+
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    parse(input).map(spin_load)
+}
+
+fn north_load(input: &str) -> usize {
+    let mut matrix = parse_matrix(input);
     let coords =
-        get_matrix_coords(matrix.width, matrix.height, Direction::North);
-    tilt_matrix(&coords, &mut matrix.matrix);
-    calculate_load(&matrix.matrix)
+        get_matrix_coords(matrix.width(), matrix.height(), Direction::North);
+    tilt_matrix(&coords, &mut matrix);
+    calculate_load(&matrix)
+}
+
+/// The spin cycles part 2 asks for.
+pub const SPINS: usize = 1000000000;
+
+fn spin_load(input: &str) -> usize {
+    let orbit = spin_orbit(parse_matrix(input));
+    calculate_load(orbit.at(SPINS))
 }
 
-pub fn part2(input: &str) -> usize {
-    let mut matrix = Matrix::new(input);
-    let cycle = [
+type SpinCycle = [(Direction, Vec<Vec<Coord>>); 4];
+
+/// The tilts of one spin cycle, each with the lines of coords it rolls
+/// rocks along.
+fn spin_cycle(matrix: &Grid<char>) -> SpinCycle {
+    [
         Direction::North,
         Direction::West,
         Direction::South,
         Direction::East,
     ]
-    .map(|d| (d, get_matrix_coords(matrix.width, matrix.height, d)));
+    .map(|d| (d, get_matrix_coords(matrix.width(), matrix.height(), d)))
+}
+
+/// The platform after each spin cycle, until it comes back to an earlier
+/// state.
+fn spin_orbit(matrix: Grid<char>) -> Orbit<Grid<char>> {
+    let cycle = spin_cycle(&matrix);
+    let orbit = Orbit::new(matrix, |matrix| {
+        let mut matrix = matrix.clone();
+        for (_direction, coords) in cycle.iter() {
+            tilt_matrix(coords, &mut matrix);
+        }
+        trace!(load = calculate_load(&matrix));
+        matrix
+    });
+    let Cycle { start, length } = orbit.cycle();
+    debug!(start, length, "found a repeating cycle");
+    orbit
+}
 
-    let mut cached: HashMap<(String, Direction), usize> = HashMap::new();
-    let mut i = 0;
-    let mut repeat = 0;
-    let mut start = 0;
+/// The platform spun until it repeats, replayed one tilt at a time.
+pub struct Tilts {
+    frames: Vec<(String, String)>,
+}
 
-    'outer: while i <= 1000000000 {
+pub fn replay(input: &str) -> Result<Tilts, ParseError> {
+    let matrix = parse_matrix(parse(input)?);
+    let mut frames = vec![("start".to_string(), matrix.to_string())];
+    let cycle = spin_cycle(&matrix);
+    for (i, matrix) in spin_orbit(matrix).states().iter().enumerate() {
+        let mut matrix = matrix.clone();
         for (direction, coords) in cycle.iter() {
-            tilt_matrix(coords, &mut matrix.matrix);
-            if cached.contains_key(&(matrix.to_string(), *direction)) {
-                start = cached[&(matrix.to_string(), *direction)];
-                repeat = i - start;
-                break 'outer;
-            }
-            cached.insert((matrix.to_string(), *direction), i);
+            tilt_matrix(coords, &mut matrix);
+            let caption = format!("cycle {}, tilted {:?}", i + 1, direction);
+            frames.push((caption, matrix.to_string()));
         }
-        i += 1;
     }
+    Ok(Tilts { frames })
+}
 
-    let mut matrix = Matrix::new(input);
-    for _ in 0..(1000000000 - start) % repeat + start {
-        for (_direction, coords) in cycle.iter() {
-            tilt_matrix(coords, &mut matrix.matrix);
-        }
+impl Replay for Tilts {
+    fn len(&self) -> usize {
+        self.frames.len()
     }
 
-    calculate_load(&matrix.matrix)
-}
+    fn render(&self, frame: usize) -> String {
+        self.frames[frame].1.clone()
+    }
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
-enum Direction {
-    North,
-    West,
-    East,
-    South,
+    fn caption(&self, frame: usize) -> String {
+        self.frames[frame].0.clone()
+    }
 }
 
-#[derive(Debug, Clone)]
-struct Matrix {
-    matrix: Vec<Vec<char>>,
-    width: usize,
-    height: usize,
+/// Where the rocks are after a number of spin cycles, drawn as an image.
+pub struct Platform {
+    matrix: Grid<char>,
+    spins: usize,
 }
 
-impl Display for Matrix {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in self.matrix.iter() {
-            writeln!(f, "{}", row.iter().collect::<String>())?;
-        }
-        Ok(())
-    }
+pub fn picture(input: &str, spins: usize) -> Result<Platform, ParseError> {
+    let orbit = spin_orbit(parse_matrix(parse(input)?));
+    Ok(Platform {
+        matrix: orbit.at(spins).clone(),
+        spins,
+    })
 }
 
-type Coord = (usize, usize);
+const SAND: Rgb = Rgb(235, 225, 200);
+const CUBE: Rgb = Rgb(110, 110, 110);
+const ROUND: Rgb = Rgb(200, 90, 40);
+
+impl Picture for Platform {
+    fn size(&self) -> (usize, usize) {
+        (self.matrix.height(), self.matrix.width())
+    }
 
-impl Matrix {
-    fn new(input: &str) -> Matrix {
-        let width = input.lines().next().unwrap().len();
-        let height = input.lines().count();
-        let matrix = input
-            .lines()
-            .map(|l| l.chars().collect::<Vec<char>>())
-            .collect();
-        Matrix {
-            height,
-            width,
-            matrix,
+    fn style(&self, m: usize, n: usize) -> Style {
+        match self.matrix[Coord::new(m, n)] {
+            '#' => Style::fill(CUBE),
+            'O' => Style::fill(SAND).with_overlay(ROUND),
+            _ => Style::fill(SAND),
         }
     }
+
+    fn title(&self) -> String {
+        format!(
+            "load {} after {} spin cycles",
+            calculate_load(&self.matrix),
+            self.spins
+        )
+    }
+}
+
+fn parse_matrix(input: &str) -> Grid<char> {
+    input.parse().expect("should be a rectangular platform")
 }
 
 fn get_matrix_coords(
@@ -96,61 +151,48 @@ fn get_matrix_coords(
 ) -> Vec<Vec<Coord>> {
     match direction {
         Direction::North => (0..width)
-            .map(|n| (0..height).map(move |m| (m, n)).collect::<Vec<Coord>>())
+            .map(|n| (0..height).map(move |m| Coord::new(m, n)).collect())
             .collect(),
         Direction::West => (0..height)
-            .map(|m| (0..width).map(move |n| (m, n)).collect::<Vec<Coord>>())
+            .map(|m| (0..width).map(move |n| Coord::new(m, n)).collect())
             .collect(),
         Direction::East => (0..height)
-            .map(|m| {
-                (0..width)
-                    .rev()
-                    .map(move |n| (m, n))
-                    .collect::<Vec<Coord>>()
-            })
+            .map(|m| (0..width).rev().map(move |n| Coord::new(m, n)).collect())
             .collect(),
         Direction::South => (0..width)
             .map(|n| {
-                (0..height)
-                    .rev()
-                    .map(move |m| (m, n))
-                    .collect::<Vec<Coord>>()
+                (0..height).rev().map(move |m| Coord::new(m, n)).collect()
             })
             .collect(),
     }
 }
 
-fn calculate_load(matrix: &Vec<Vec<char>>) -> usize {
+fn calculate_load(matrix: &Grid<char>) -> usize {
     matrix
-        .iter()
+        .rows()
         .rev()
         .enumerate()
         .map(|(i, line)| line.iter().filter(|n| **n == 'O').count() * (i + 1))
         .sum()
 }
 
-fn tilt_matrix(coords_vecs: &Vec<Vec<Coord>>, matrix: &mut [Vec<char>]) {
+fn tilt_matrix(coords_vecs: &[Vec<Coord>], matrix: &mut Grid<char>) {
     for line in coords_vecs {
         let mut i = 0;
         while i < line.len() {
-            let (m_i, n_i) = line[i];
-            if matrix[m_i][n_i] == 'O' || matrix[m_i][n_i] == '#' {
+            if matrix[line[i]] == 'O' || matrix[line[i]] == '#' {
                 i += 1;
                 continue;
             } else {
                 let mut j = i + 1;
                 while j < line.len() {
-                    let (m_j, n_j) = line[j];
-
-                    if matrix[m_j][n_j] == 'O' {
-                        let tmp = matrix[m_i][n_i];
-                        matrix[m_i][n_i] = matrix[m_j][n_j];
-                        matrix[m_j][n_j] = tmp;
+                    if matrix[line[j]] == 'O' {
+                        matrix.swap(line[i], line[j]);
                         break;
-                    } else if matrix[m_j][n_j] == '.' {
+                    } else if matrix[line[j]] == '.' {
                         j += 1;
                         continue;
-                    } else if matrix[m_j][n_j] == '#' {
+                    } else if matrix[line[j]] == '#' {
                         i = j;
                         break;
                     }
@@ -161,46 +203,92 @@ fn tilt_matrix(coords_vecs: &Vec<Vec<Coord>>, matrix: &mut [Vec<char>]) {
     }
 }
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(north_load(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(spin_load(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{part1, part2};
+    use crate::{part1, part2, picture, replay, CUBE, ROUND, SAND, SPINS};
+    use common::replay::Replay;
     use indoc::indoc;
+    use picture::{Picture, Style};
+
+    const EXAMPLE: &str = include_str!("../examples/example1.txt");
 
     #[test]
     fn test_part1() {
-        let input = indoc! {
-            r#"
-            O....#....
-            O.OO#....#
-            .....##...
-            OO.#O....O
-            .O.....O#.
-            O.#..O.#.#
-            ..O..#O..O
-            .......O..
-            #....###..
-            #OO..#....
-            "#
-        };
-        assert_eq!(part1(input), 136);
+        assert_eq!(part1(EXAMPLE), Ok(136));
     }
 
     #[test]
     fn test_part2() {
-        let input = indoc! {
-            r#"
-            O....#....
-            O.OO#....#
-            .....##...
-            OO.#O....O
-            .O.....O#.
-            O.#..O.#.#
-            ..O..#O..O
-            .......O..
-            #....###..
-            #OO..#....
-            "#
-        };
-        assert_eq!(part2(input), 64);
+        assert_eq!(part2(EXAMPLE), Ok(64));
+    }
+
+    #[test]
+    fn test_replay() {
+        let tilts = replay("O.\n.#\n").unwrap();
+        assert_eq!(tilts.caption(0), "start");
+        assert_eq!(tilts.render(0), "O.\n.#\n");
+        assert_eq!(tilts.caption(3), "cycle 1, tilted South");
+        assert_eq!(tilts.render(3), "..\nO#\n");
+        // The second cycle ends where the first did.
+        assert_eq!(tilts.len(), 9);
+        assert_eq!(tilts.render(8), tilts.render(4));
+    }
+
+    #[test]
+    fn test_picture() {
+        // The puzzle's drawing of the platform after one cycle.
+        let after_one = indoc! {"
+            .....#....
+            ....#...O#
+            ...OO##...
+            .OO#......
+            .....OOO#.
+            .O#...O#.#
+            ....O#....
+            ......OOOO
+            #...O###..
+            #..OO#....
+        "};
+        let platform = picture(EXAMPLE, 1).unwrap();
+        assert_eq!(platform.size(), (10, 10));
+        for (m, row) in after_one.lines().enumerate() {
+            for (n, ch) in row.chars().enumerate() {
+                let expected = match ch {
+                    '#' => Style::fill(CUBE),
+                    'O' => Style::fill(SAND).with_overlay(ROUND),
+                    _ => Style::fill(SAND),
+                };
+                assert_eq!(platform.style(m, n), expected, "({m}, {n})");
+            }
+        }
+        assert_eq!(
+            picture(EXAMPLE, SPINS).unwrap().title(),
+            "load 64 after 1000000000 spin cycles"
+        );
+    }
+
+    #[test]
+    fn test_malformed_platform() {
+        let err = part1("O.#\n.x.\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 2));
     }
 }
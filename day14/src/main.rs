@@ -1,7 +1,8 @@
-use std::fmt::Display;
-
+use common::embedded_input;
+use common::input::load_or_exit;
 fn main() {
-    let input = include_str!("input.txt");
-    println!("{:?}", day14::part1(input));
-    println!("{:?}", day14::part2(input));
+    common::trace::init();
+    let input = &load_or_exit(embedded_input!());
+    println!("{}", day14::part1(input).unwrap_or_else(|err| err.exit()));
+    println!("{}", day14::part2(input).unwrap_or_else(|err| err.exit()));
 }
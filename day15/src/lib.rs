@@ -0,0 +1,175 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+
+use common::error::{ParseError, SolveError};
+use common::{Answer, Solution};
+
+/// Checks that every step is a label followed by `-`, or by `=` and a focal
+/// length from 1 to 9.
+pub fn parse(input: &str) -> Result<&str, ParseError> {
+    for step in input.trim().split(',') {
+        let label = step
+            .find(|c: char| !c.is_ascii_lowercase())
+            .map_or(step, |i| &step[..i]);
+        let operation = &step[label.len()..];
+        let (at, error) = match operation.as_bytes() {
+            _ if label.is_empty() => (step, "expected a label"),
+            b"-" | [b'=', b'1'..=b'9'] => continue,
+            [b'=', ..] => (&operation[1..], "expected a focal length"),
+            _ => (operation, "expected = or -"),
+        };
+        return Err(ParseError::at_slice(input, at, error));
+    }
+    Ok(input)
+}
+
+pub fn part1(input: &str) -> Result<u32, ParseError> {
+    parse(input).map(hash_sum)
+}
+
+fn hash_sum(input: &str) -> u32 {
+    input.trim().split(',').map(|s| hash(s, 0)).sum()
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Step<'a> {
+    label: &'a str,
+    focal: &'a str,
+}
+
+impl Display for Step<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[{} {}]", self.label, self.focal)?;
+        Ok(())
+    }
+}
+
+enum StepType<'a> {
+    Add(Step<'a>),
+    Remove(Step<'a>),
+}
+
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    parse(input).map(focusing_power)
+}
+
+fn focusing_power(input: &str) -> usize {
+    let mut boxes = vec![VecDeque::<Step<'_>>::new(); 256];
+    input
+        .trim()
+        .split(',')
+        .map(|origin| origin.split_once(['=', '-']).unwrap())
+        .map(|(label, focal)| {
+            let step = Step { label, focal };
+            (
+                match focal {
+                    "" => StepType::Remove(step),
+                    _ => StepType::Add(step),
+                },
+                hash(label, 0) as usize,
+            )
+        })
+        .for_each(|(step_type, box_n)| {
+            let tbox = &mut boxes[box_n];
+            match step_type {
+                // 1. If there is already a lens in the box with the same label,
+                // replace the old lens with the new lens: remove the old lens and put the new lens in its place,
+                // not moving any other lenses in the box.
+
+                // 2. If there is not already a lens in the box with the same label,
+                // add the lens to the box immediately behind any lenses already in the box.
+                // Don't move any of the other lenses when you do this.
+                // If there aren't any lenses in the box,
+                // the new lens goes all the way to the front of the box.
+                StepType::Add(step) => {
+                    if let Some(pos) =
+                        tbox.iter().position(|s| step.label == s.label)
+                    {
+                        tbox[pos] = step;
+                    } else {
+                        tbox.push_back(step);
+                    }
+                }
+                StepType::Remove(step) => {
+                    if let Some(pos) =
+                        tbox.iter().position(|s| step.label == s.label)
+                    {
+                        tbox.remove(pos);
+                    }
+                }
+            }
+        });
+    boxes
+        .iter()
+        .enumerate()
+        .map(|(b_index, b)| {
+            b.iter()
+                .enumerate()
+                .map(|(index, step)| {
+                    step.focal.parse::<usize>().expect("should be a number")
+                        * (index + 1)
+                        * (b_index + 1)
+                })
+                .sum::<usize>()
+        })
+        .sum::<usize>()
+}
+
+fn hash(input: &str, value: u32) -> u32 {
+    input
+        .chars()
+        .fold(value, |acc, ch| ((ch as u32 + acc) * 17) % 256)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(hash_sum(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(focusing_power(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/example1.txt");
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash("HASH", 0), 52);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE), Ok(1320));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE), Ok(145));
+    }
+
+    #[test]
+    fn test_malformed_step() {
+        let err = part2("rn=1,cm-,qp=x").unwrap_err();
+        assert_eq!(
+            (err.message(), err.column()),
+            ("expected a focal length", 13)
+        );
+        let err = part2("rn=1,=3").unwrap_err();
+        assert_eq!((err.message(), err.column()), ("expected a label", 6));
+        assert!(part2("rn=1,cm").is_err());
+    }
+}
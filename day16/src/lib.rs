@@ -0,0 +1,352 @@
+use common::error::{check_grid, ParseError, SolveError};
+use common::replay::Replay;
+use common::snapshot::Snapshot;
+use common::{Answer, Solution};
+use grid::{Coord, Direction, Grid};
+use picture::{Picture, Rgb, Style};
+use search::{bfs, Paths};
+use std::collections::{HashMap, HashSet};
+use tracing::{debug_span, enabled, trace, Level};
+
+fn energized(contraption: &Contraption, start_light: Light) -> usize {
+    beam(contraption, start_light)
+        .distances()
+        .map(|(light, _)| light.curr)
+        .collect::<HashSet<Coord>>()
+        .len()
+}
+
+fn energized_from_corner(contraption: &Contraption) -> usize {
+    energized(contraption, START_LIGHT)
+}
+
+fn most_energized(contraption: &Contraption) -> usize {
+    let (height, width) =
+        (contraption.grid.height(), contraption.grid.width());
+    let rows: Vec<Light> = (0..height)
+        .flat_map(|m| {
+            vec![
+                Light {
+                    curr: Coord::new(m, 0),
+                    direction: Direction::East,
+                },
+                Light {
+                    curr: Coord::new(m, width - 1),
+                    direction: Direction::West,
+                },
+            ]
+        })
+        .collect();
+    let cols: Vec<Light> = (0..width)
+        .flat_map(|n| {
+            vec![
+                Light {
+                    curr: Coord::new(0, n),
+                    direction: Direction::South,
+                },
+                Light {
+                    curr: Coord::new(height - 1, n),
+                    direction: Direction::North,
+                },
+            ]
+        })
+        .collect();
+
+    rows.iter()
+        .chain(&cols)
+        .map(|light| energized(contraption, *light))
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn part1(input: &str) -> Result<usize, ParseError> {
+    parse(input).map(|contraption| energized_from_corner(&contraption))
+}
+
+pub fn part2(input: &str) -> Result<usize, ParseError> {
+    parse(input).map(|contraption| most_energized(&contraption))
+}
+
+/// The beam entering the top-left corner, heading east.
+const START_LIGHT: Light = Light {
+    curr: Coord::new(0, 0),
+    direction: Direction::East,
+};
+
+/// Light entering the tile `curr`, heading `direction`.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+struct Light {
+    curr: Coord,
+    direction: Direction,
+}
+
+impl Light {
+    /// The ways the tile sends the light on: turned by a mirror, split in
+    /// two by a splitter it hits side on, or straight ahead.
+    fn turns(&self, tile: char) -> [Option<Direction>; 2] {
+        use Direction::{East, North, South, West};
+        let direction = self.direction;
+        let turned = match (tile, direction) {
+            ('|', East | West) => return [Some(North), Some(South)],
+            ('-', North | South) => return [Some(East), Some(West)],
+            ('/', East | West) | ('\\', North | South) => {
+                direction.turn_left()
+            }
+            ('/', North | South) | ('\\', East | West) => {
+                direction.turn_right()
+            }
+            _ => direction,
+        };
+        [Some(turned), None]
+    }
+
+    /// The lights this one turns into on the next tiles: none past the edge
+    /// and two where a splitter splits it.
+    fn beams<'a>(
+        &self,
+        grid: &'a Grid<char>,
+    ) -> impl Iterator<Item = Light> + 'a {
+        let curr = self.curr;
+        self.turns(grid[curr]).into_iter().flatten().filter_map(
+            move |direction| {
+                let next =
+                    curr.step(direction).filter(|&c| grid.contains(c))?;
+                Some(Light {
+                    curr: next,
+                    direction,
+                })
+            },
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Contraption {
+    grid: Grid<char>,
+}
+
+/// Reads the contraption, checking it is a rectangle of empty space and
+/// mirrors.
+pub fn parse(input: &str) -> Result<Contraption, ParseError> {
+    check_grid(input, input.lines(), "./|-\\")?;
+    let grid = input.parse().expect("should be a rectangular contraption");
+    Ok(Contraption { grid })
+}
+
+/// The contraption with its energized tiles drawn as `#`.
+fn draw(
+    contraption: &Contraption,
+    energized: impl Fn(&Coord) -> bool,
+) -> String {
+    contraption
+        .grid
+        .map(|coord, &tile| if energized(&coord) { '#' } else { tile })
+        .to_string()
+}
+
+/// Follows the beam from `start_light` through every light it turns into,
+/// each one's distance being the wave it appears in, the start's 0.
+fn beam(contraption: &Contraption, start_light: Light) -> Paths<Light, usize> {
+    let _span = debug_span!("beam", start = ?start_light).entered();
+    let waves = bfs(start_light, |light| light.beams(&contraption.grid));
+    if enabled!(Level::TRACE) {
+        // One event per BFS layer, the lights of one wave.
+        let mut lights = vec![];
+        for (_, wave) in waves.distances() {
+            if lights.len() <= wave {
+                lights.resize(wave + 1, 0);
+            }
+            lights[wave] += 1;
+        }
+        let mut visited = 0;
+        for (wave, lights) in lights.into_iter().enumerate() {
+            visited += lights;
+            trace!(wave, lights, visited);
+        }
+    }
+    trace!(
+        "energized after {} waves\n{}",
+        waves.distances().map(|(_, wave)| wave).max().unwrap_or(0),
+        draw(contraption, {
+            let seen: HashSet<Coord> =
+                waves.distances().map(|(light, _)| light.curr).collect();
+            move |coord| seen.contains(coord)
+        })
+    );
+    waves
+}
+
+/// The beam entering from the top-left corner, replayed one wave at a time.
+pub struct Beams {
+    contraption: Contraption,
+    energized_at: HashMap<Coord, usize>,
+    waves: usize,
+}
+
+pub fn replay(input: &str) -> Result<Beams, ParseError> {
+    let contraption = parse(input)?;
+    let mut energized_at = HashMap::new();
+    // Frame 0 is the contraption before the beam enters, so the tiles lit
+    // in wave 0 show from frame 1.
+    for (light, wave) in beam(&contraption, START_LIGHT).distances() {
+        let at = energized_at.entry(light.curr).or_insert(wave + 1);
+        *at = (*at).min(wave + 1);
+    }
+    let waves = energized_at.values().copied().max().unwrap_or(0);
+    Ok(Beams {
+        contraption,
+        energized_at,
+        waves,
+    })
+}
+
+/// The beam from the top-left corner at ten points on its way, for the
+/// visualization server.
+pub fn snapshots(input: &str) -> Result<Vec<Snapshot>, ParseError> {
+    let beams = replay(input)?;
+    let tiles = draw(&beams.contraption, |_| false);
+    let mut waves: Vec<usize> =
+        (1..=10).map(|k| k * beams.waves / 10).collect();
+    waves.dedup();
+    Ok(waves
+        .into_iter()
+        .map(|wave| {
+            let energized = beams
+                .energized_at
+                .iter()
+                .filter(|(_, at)| **at <= wave)
+                .map(|(coord, _)| (coord.m, coord.n));
+            Snapshot::new(format!("wave {} of {}", wave, beams.waves), &tiles)
+                .with_layer("energized", energized)
+        })
+        .collect())
+}
+
+impl Replay for Beams {
+    fn len(&self) -> usize {
+        self.waves + 1
+    }
+
+    fn render(&self, frame: usize) -> String {
+        draw(&self.contraption, |coord| {
+            self.energized_at
+                .get(coord)
+                .is_some_and(|wave| *wave <= frame)
+        })
+    }
+
+    fn caption(&self, frame: usize) -> String {
+        format!("wave {}", frame)
+    }
+}
+
+const ENERGIZED: Rgb = Rgb(255, 220, 0);
+const DARK: Rgb = Rgb(30, 30, 50);
+
+/// The tiles the beam from the top-left corner energizes in the end, with
+/// the mirrors and splitters drawn over them.
+impl Picture for Beams {
+    fn size(&self) -> (usize, usize) {
+        let grid = &self.contraption.grid;
+        (grid.height(), grid.width())
+    }
+
+    fn style(&self, m: usize, n: usize) -> Style {
+        let coord = Coord::new(m, n);
+        let style = if self.energized_at.contains_key(&coord) {
+            Style::fill(ENERGIZED)
+        } else {
+            Style::fill(DARK)
+        };
+        match self.contraption.grid[coord] {
+            '.' => style,
+            mirror => style.with_glyph(mirror),
+        }
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "{} tiles energized in {} waves",
+            self.energized_at.len(),
+            self.waves
+        )
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Input<'a> = Contraption;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(energized_from_corner(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(most_energized(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/example1.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE), Ok(46));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE), Ok(51));
+    }
+
+    #[test]
+    fn test_replay() {
+        let beams = replay(".\\\n..\n").unwrap();
+        assert_eq!(beams.len(), 4);
+        assert_eq!(beams.render(0), ".\\\n..\n");
+        assert_eq!(beams.render(1), "#\\\n..\n");
+        assert_eq!(beams.render(2), "##\n..\n");
+        assert_eq!(beams.render(beams.len() - 1), "##\n.#\n");
+        assert_eq!(part1(".\\\n..\n"), Ok(3));
+    }
+
+    #[test]
+    fn test_snapshots() {
+        let snapshots = snapshots(".\\\n..\n").unwrap();
+        assert_eq!(snapshots.len(), 4);
+        assert_eq!(snapshots[0].rows, [".\\", ".."]);
+        let last = &snapshots[3];
+        assert_eq!(last.caption, "wave 3 of 3");
+        assert_eq!(last.layers[0].cells, [(0, 0), (0, 1), (1, 1)]);
+    }
+
+    #[test]
+    fn test_picture() {
+        let beams = replay(".\\\n..\n").unwrap();
+        assert_eq!(beams.size(), (2, 2));
+        assert_eq!(beams.title(), "3 tiles energized in 3 waves");
+        assert_eq!(beams.style(0, 1), Style::fill(ENERGIZED).with_glyph('\\'));
+        assert_eq!(beams.style(1, 0), Style::fill(DARK));
+        assert_eq!(beams.style(1, 1), Style::fill(ENERGIZED));
+        assert!(beams.to_svg(8).contains(">\\</text>"));
+    }
+
+    #[test]
+    fn test_lava_disco() {
+        let err = part1(".|.\n.x.\n...\n").unwrap_err();
+        assert_eq!(err.message(), "expected one of \"./|-\\\\\", not 'x'");
+        assert_eq!((err.line(), err.column()), (2, 2));
+        let err = part1("..\n.\n").unwrap_err();
+        assert_eq!(err.message(), "expected 2 tiles in every row");
+        assert_eq!((err.line(), err.column()), (2, 2));
+    }
+}
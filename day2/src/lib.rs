@@ -0,0 +1,140 @@
+use common::error::{finish, ParseError, SolveError};
+use common::{Answer, Solution};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{space1, u32 as nom_u32};
+use nom::error::Error;
+use nom::multi::separated_list1;
+use nom::sequence::{pair, preceded, separated_pair};
+use nom::IResult;
+use parsers::label_number;
+
+#[derive(Default)]
+pub struct Game {
+    id: u32,
+    blue: u32,
+    red: u32,
+    green: u32,
+}
+
+type ColorPairs<'a> = Vec<(u32, &'a str)>;
+
+pub fn parse_game(
+    input: &str,
+) -> IResult<&str, (u32, Vec<ColorPairs<'_>>), Error<&str>> {
+    pair(label_number("Game"), parse_colors)(input)
+}
+
+fn parse_colors(
+    input: &str,
+) -> IResult<&str, Vec<ColorPairs<'_>>, Error<&str>> {
+    separated_list1(tag(";"), parse_color_pairs)(input)
+}
+
+fn parse_color_pairs(
+    input: &str,
+) -> IResult<&str, ColorPairs<'_>, Error<&str>> {
+    separated_list1(tag(","), preceded(space1, parse_color_number))(input)
+}
+
+fn parse_color_number(input: &str) -> IResult<&str, (u32, &str), Error<&str>> {
+    separated_pair(
+        nom_u32,
+        tag(" "),
+        alt((tag("blue"), tag("red"), tag("green"))),
+    )(input)
+}
+
+impl Game {
+    /// Parses one `line` of `input`; errors point into `input`.
+    fn new(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (id, pairs) = finish(input, parse_game(line))?;
+        let (mut red, mut green, mut blue) = (0, 0, 0);
+        for colors in pairs {
+            for (number, color) in colors {
+                match color {
+                    "red" => red = red.max(number),
+                    "blue" => blue = blue.max(number),
+                    "green" => green = green.max(number),
+                    _ => unreachable!(),
+                }
+            }
+        }
+        Ok(Game {
+            id,
+            red,
+            green,
+            blue,
+        })
+    }
+
+    fn power_of_game(&self) -> u128 {
+        u128::from(self.red) * u128::from(self.green) * u128::from(self.blue)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    input.lines().map(|line| Game::new(input, line)).collect()
+}
+
+fn sum_possible_ids(games: &[Game]) -> u64 {
+    games
+        .iter()
+        .filter(|g| g.red <= 12 && g.green <= 13 && g.blue <= 14)
+        .map(|g| u64::from(g.id))
+        .sum()
+}
+
+fn sum_powers(games: &[Game]) -> u128 {
+    games.iter().map(|g| g.power_of_game()).sum()
+}
+
+pub fn part1(input: &str) -> Result<u64, ParseError> {
+    parse(input).map(|games| sum_possible_ids(&games))
+}
+
+pub fn part2(input: &str) -> Result<u128, ParseError> {
+    parse(input).map(|games| sum_powers(&games))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Input<'a> = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(sum_possible_ids(input).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(sum_powers(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/example1.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Ok(8), part1(EXAMPLE))
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Ok(2286), part2(EXAMPLE))
+    }
+
+    #[test]
+    fn test_malformed_game() {
+        let err = part1("Game 1: 3 blue\nGame 2: 4 purple\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 11));
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use common::error::{ParseError, SolveError};
+use common::{Answer, Solution};
+use regex::Regex;
+
+#[derive(Debug, Hash, Clone)]
+struct Number {
+    number: u64,
+    row: usize,
+    start: usize,
+    end: usize,
+}
+
+pub fn gear_sum(input: &str) -> (u128, u128) {
+    let matrix: Vec<Vec<char>> =
+        input.lines().map(|line| line.chars().collect()).collect();
+    let mut bucket: HashMap<usize, Vec<Number>> = HashMap::new();
+    // m +- 1
+    for (row_number, row) in matrix.iter().enumerate() {
+        let mut temp = String::from("");
+        let mut start = 0;
+        for (index, ch) in row.iter().enumerate() {
+            if ch.is_ascii_digit() {
+                if temp.is_empty() {
+                    start = index;
+                }
+                temp.push(*ch);
+            } else if !temp.is_empty() {
+                let number = Number {
+                    start,
+                    row: row_number,
+                    end: index - 1,
+                    number: temp.parse::<u64>().unwrap(),
+                };
+                bucket.entry(row_number).or_default().push(number);
+                temp = String::from("");
+            }
+        }
+        if !temp.is_empty() {
+            let number = Number {
+                start,
+                row: row_number,
+                end: row.len() - 1,
+                number: temp.parse::<u64>().unwrap(),
+            };
+            bucket.entry(row_number).or_default().push(number);
+        }
+    }
+
+    let mut chars: HashMap<(usize, usize), Vec<u64>> = HashMap::new();
+    for (row_number, row) in matrix.iter().enumerate() {
+        for (index, ch) in row.iter().enumerate() {
+            if ch.is_ascii_digit() || ch == &'.' {
+                continue;
+            }
+            for diff in [-1, 0, 1] {
+                if let Some(row) = row_number.checked_add_signed(diff) {
+                    bucket
+                        .entry(row)
+                        .or_default()
+                        .iter()
+                        .filter(|n| {
+                            let start = n.start as i32;
+                            let end = n.end as i32;
+                            start.max(index as i32 - 1)
+                                <= end.min(index as i32 + 1)
+                        })
+                        .for_each(|n| {
+                            chars
+                                .entry((row_number, index))
+                                .or_default()
+                                .push(n.number);
+                        })
+                }
+            }
+        }
+    }
+
+    let part1 = chars.values().flatten().map(|&n| n as u128).sum();
+
+    let part2 = chars
+        .values()
+        .filter(|v| v.len() == 2)
+        .map(|v| v.iter().map(|&n| n as u128).product::<u128>())
+        .sum();
+
+    (part1, part2)
+}
+
+type CharPos = (usize, usize);
+
+#[derive(Debug)]
+struct Grid<'a> {
+    grid: Vec<&'a str>,
+}
+
+impl<'a> Display for Grid<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.grid.iter() {
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+impl<'a> Grid<'a> {
+    fn get_numbers_bucket(&self) -> HashMap<usize, Vec<Number>> {
+        let num_regex = Regex::new(r"\d+").unwrap();
+        self.grid
+            .iter()
+            .enumerate()
+            .map(|(r, line)| {
+                num_regex
+                    .find_iter(line)
+                    .map(move |m| {
+                        (
+                            r,
+                            Number {
+                                number: m.as_str().parse().unwrap(),
+                                start: m.start(),
+                                end: m.end() - 1,
+                                row: r,
+                            },
+                        )
+                    })
+                    .collect::<Vec<(usize, Number)>>()
+            })
+            .fold(HashMap::new(), |mut acc, v| {
+                v.iter().for_each(|(r, n)| {
+                    acc.entry(*r).or_default().push(n.clone())
+                });
+                acc
+            })
+    }
+
+    fn get_adjacent(
+        &self,
+        bucket: &HashMap<usize, Vec<Number>>,
+    ) -> HashMap<CharPos, Vec<u64>> {
+        let mut chars: HashMap<CharPos, Vec<u64>> = HashMap::new();
+        self.grid.iter().enumerate().for_each(|(r, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|(_, ch)| !ch.is_ascii_digit() && ch != &'.')
+                .for_each(|(c, _)| {
+                    for diff in [-1, 0, 1] {
+                        if let Some(row) = r.checked_add_signed(diff) {
+                            bucket
+                                .get(&row)
+                                .unwrap_or(&vec![])
+                                .iter()
+                                .filter(|n| {
+                                    let c = c as i32;
+                                    (c - 1).max(n.start as i32)
+                                        <= (c + 1).min(n.end as i32)
+                                })
+                                .for_each(|n| {
+                                    chars
+                                        .entry((r, c))
+                                        .or_default()
+                                        .push(n.number);
+                                });
+                        }
+                    }
+                })
+        });
+        chars
+    }
+}
+
+pub fn gear_sum_v2(input: &str) -> (u128, u128) {
+    let grid = Grid {
+        grid: input.lines().collect(),
+    };
+    let bucket = grid.get_numbers_bucket();
+    let adjacent = grid.get_adjacent(&bucket);
+
+    (
+        adjacent.values().flatten().map(|&n| n as u128).sum(),
+        adjacent
+            .values()
+            .filter(|v| v.len() == 2)
+            .map(|v| v.iter().map(|&n| n as u128).product::<u128>())
+            .sum(),
+    )
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input<'a> = &'a str;
+
+    /// Part numbers have to fit in a `u32`, so that a gear ratio fits in a
+    /// `u64` and the sums, taken as `u128`, cannot overflow.
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        let number = Regex::new("[0-9]+").expect("should be a valid regex");
+        let too_big = number
+            .find_iter(input)
+            .find(|m| m.as_str().parse::<u32>().is_err());
+        match too_big {
+            Some(m) => {
+                Err(ParseError::at(input, m.start(), "number out of range"))
+            }
+            None => Ok(input),
+        }
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(gear_sum(input).0.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(gear_sum(input).1.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Part;
+
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/example1.txt");
+
+    #[test]
+    fn test_gear_sum() {
+        assert_eq!((4361, 467835), gear_sum(EXAMPLE))
+    }
+
+    #[test]
+    fn test_gear_sum_v2() {
+        assert_eq!((4361, 467835), gear_sum_v2(EXAMPLE))
+    }
+
+    #[test]
+    fn test_number_out_of_range() {
+        let err = Day3::parse("1*\n*99999999999\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 2));
+    }
+
+    #[test]
+    fn test_sums_past_u64() {
+        let input = "4294967295*4294967295\n\
+                     .....................\n\
+                     4294967295*4294967295\n";
+        let sums = (4 * 4294967295, 2 * 4294967295u128.pow(2));
+        assert_eq!(gear_sum(input), sums);
+        assert_eq!(gear_sum_v2(input), sums);
+        assert_eq!(Day3::solve(input, Part::Part2), Ok(Answer::from(sums.1)));
+    }
+}
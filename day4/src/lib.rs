@@ -0,0 +1,146 @@
+use common::error::{finish, ParseError, SolveError};
+use common::{Answer, Solution};
+use nom::bytes::complete::tag;
+use nom::character::complete::u32 as nom_u32;
+use nom::sequence::separated_pair;
+use nom::IResult;
+use parsers::{label_number, numbers};
+
+#[derive(Debug)]
+pub struct Card {
+    id: u32,
+    winning_numbers: Vec<u32>,
+    owning_numbers: Vec<u32>,
+}
+
+pub fn parse_card(input: &str) -> IResult<&str, Card> {
+    let (input, (id, (winning_numbers, owning_numbers))) = separated_pair(
+        label_number("Card"),
+        tag(" "),
+        separated_pair(numbers(nom_u32), tag(" | "), numbers(nom_u32)),
+    )(input)?;
+    Ok((
+        input,
+        Card {
+            id,
+            winning_numbers,
+            owning_numbers,
+        },
+    ))
+}
+
+pub fn parse(input: &str) -> Result<Vec<Card>, ParseError> {
+    input
+        .lines()
+        .map(|line| finish(input, parse_card(line)))
+        .collect()
+}
+
+impl Card {
+    fn matches(&self) -> usize {
+        self.owning_numbers
+            .iter()
+            .filter(|n| self.winning_numbers.contains(n))
+            .count()
+    }
+}
+
+fn points(cards: &[Card]) -> Result<u64, SolveError> {
+    cards
+        .iter()
+        .try_fold(0u64, |sum, card| match card.matches() {
+            0 => Some(sum),
+            n => 1u64
+                .checked_shl((n - 1) as u32)
+                .and_then(|points| sum.checked_add(points)),
+        })
+        .ok_or_else(|| SolveError::no_answer("too many points to count"))
+}
+
+/// How many of each card we end up with.
+fn copies(cards: &[Card]) -> Result<Vec<u32>, SolveError> {
+    let mut copies = vec![1u32; cards.len()];
+    for (i, card) in cards.iter().enumerate() {
+        if card.matches() > 0 && i + card.matches() >= cards.len() {
+            return Err(SolveError::no_answer(format!(
+                "card {} wins copies past the end of the table",
+                card.id
+            )));
+        }
+        for n in i + 1..=i + card.matches() {
+            copies[n] = copies[n].checked_add(copies[i]).ok_or_else(|| {
+                SolveError::no_answer(format!(
+                    "too many copies of card {} to count",
+                    cards[n].id
+                ))
+            })?;
+        }
+    }
+    Ok(copies)
+}
+
+fn total_cards(cards: &[Card]) -> Result<u64, SolveError> {
+    Ok(copies(cards)?.into_iter().map(u64::from).sum())
+}
+
+pub fn part1(input: &str) -> Result<u64, SolveError> {
+    points(&parse(input)?)
+}
+
+pub fn part2(input: &str) -> Result<u64, SolveError> {
+    total_cards(&parse(input)?)
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input<'a> = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(points(input)?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(total_cards(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../examples/example1.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Ok(13), part1(EXAMPLE))
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(Ok(30), part2(EXAMPLE))
+    }
+
+    #[test]
+    fn test_malformed_card() {
+        let err = parse("Card 1: 41 x | 83 86\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 11));
+    }
+
+    #[test]
+    fn test_copies_past_the_end() {
+        let input = "Card 1: 41 | 41\n";
+        assert_eq!(part1(input), Ok(1));
+        assert_eq!(
+            part2(input),
+            Err(SolveError::no_answer(
+                "card 1 wins copies past the end of the table"
+            ))
+        );
+    }
+}
@@ -1,267 +1,202 @@
 use std::ops::Range;
 
+use common::error::{finish, ParseError, SolveError};
+use common::{Answer, Solution};
 use indicatif::ProgressIterator;
+use intervals::{IntervalMap, IntervalSet};
 use itertools::Itertools;
 use nom::bytes::complete::tag;
-use nom::character::complete::{alpha1, line_ending, space1, u64 as nom_u64};
-use nom::combinator::opt;
+use nom::character::complete::{line_ending, space1, u64 as nom_u64};
+use nom::combinator::map_opt;
 use nom::error::Error;
-use nom::multi::{many1, separated_list1};
-use nom::sequence::{delimited, pair, preceded, separated_pair, terminated};
+use nom::sequence::{pair, preceded, separated_pair, terminated, tuple};
 use nom::IResult;
+use parsers::{a_to_b, lines, numbers, sections};
+use tracing::{debug, debug_span, trace};
 
 fn find_min<T: ExactSizeIterator<Item = u64>>(it: T, maps: &[Map]) -> u64 {
     it.map(|n| {
-        maps.iter().fold(n, |mut acc, m| {
-            acc = m.convert(acc);
-            acc
+        maps.iter().fold(n, |acc, m| {
+            let converted = m.convert(acc);
+            trace!(src = m.src, dst = m.dst, acc, converted);
+            converted
         })
     })
     .min()
     .unwrap()
 }
 
-pub fn part1(input: &str) -> u64 {
-    let (_, (seeds, maps)) = parse_garden(input).unwrap();
-    find_min(seeds.into_iter(), &maps)
+pub struct Almanac<'a> {
+    input: &'a str,
+    seeds: Vec<u64>,
+    maps: Vec<Map<'a>>,
 }
 
-pub fn part2_brute_force(input: &str) -> u64 {
-    let (_, (seeds, maps)) = parse_garden(input).unwrap();
-    let seeds: Vec<u64> = seeds
-        .into_iter()
+pub fn parse(input: &str) -> Result<Almanac<'_>, ParseError> {
+    let (seeds, maps) = finish(input, parse_garden(input))?;
+    Ok(Almanac { input, seeds, maps })
+}
+
+/// The seeds read as pairs of start and length, as part two does, failing
+/// when they do not pair up or hold no seed at all.
+fn seed_ranges(almanac: &Almanac) -> Result<Vec<Range<u64>>, SolveError> {
+    let input = almanac.input;
+    if !almanac.seeds.len().is_multiple_of(2) {
+        return Err(ParseError::at(
+            input,
+            0,
+            "expected seeds as pairs of start and length",
+        )
+        .into());
+    }
+    let ranges = almanac
+        .seeds
+        .iter()
         .tuples()
-        .flat_map(|t: (u64, u64)| t.0..t.1 + t.0)
-        .collect();
-    seeds
+        .map(|(&start, &len)| Some(start..start.checked_add(len)?))
+        .collect::<Option<Vec<Range<u64>>>>()
+        .ok_or_else(|| ParseError::at(input, 0, "seed range out of range"))?;
+    if ranges.iter().all(Range::is_empty) {
+        return Err(SolveError::no_answer("the seed ranges hold no seed"));
+    }
+    Ok(ranges)
+}
+
+fn lowest_location(almanac: &Almanac) -> u64 {
+    find_min(almanac.seeds.iter().copied(), &almanac.maps)
+}
+
+pub fn part1(input: &str) -> Result<u64, ParseError> {
+    parse(input).map(|almanac| lowest_location(&almanac))
+}
+
+pub fn part2_brute_force(input: &str) -> Result<u64, SolveError> {
+    let almanac = parse(input)?;
+    let ranges = seed_ranges(&almanac)?;
+    // Streamed: the real input has close to two billion seeds.
+    let count = ranges.iter().map(|r| r.end - r.start).sum();
+    let lowest = ranges
         .into_iter()
-        .progress()
+        .flatten()
+        .progress_count(count)
         .map(|n| {
-            maps.iter().fold(n, |mut acc, m| {
+            almanac.maps.iter().fold(n, |mut acc, m| {
                 acc = m.convert(acc);
                 acc
             })
         })
         .min()
-        .unwrap()
+        .expect("should have at least one seed range");
+    Ok(lowest)
 }
 
-pub fn part2(input: &str) -> u64 {
-    let (_, (seeds, maps)) = parse_garden(input).unwrap();
-    seeds
-        .into_iter()
-        .tuples()
-        .map(|t: (u64, u64)| t.0..t.1 + t.0)
-        .flat_map(|r| {
-            maps.iter().fold(vec![r], |acc, m| {
-                let mut new_acc = vec![];
-                for r in acc {
-                    new_acc.extend(m.convert_range_v2(r));
-                }
-                new_acc
-            })
-        })
-        .sorted_by(|r1, r2| r1.start.cmp(&r2.start))
-        .next()
-        .unwrap()
-        .start
+/// The lowest location of any seed in the seed ranges.
+fn lowest_location_of_ranges(almanac: &Almanac) -> Result<u64, SolveError> {
+    let seeds: IntervalSet<u64> = seed_ranges(almanac)?.into_iter().collect();
+    let _span = debug_span!("seeds", ranges = seeds.ranges().len()).entered();
+    let locations = almanac.maps.iter().fold(seeds, |acc, m| {
+        let _span = debug_span!("stage", src = m.src, dst = m.dst).entered();
+        let converted = m.ranges.apply(&acc);
+        debug!(ranges = converted.ranges().len());
+        converted
+    });
+    Ok(locations
+        .min()
+        .expect("should map every seed to a location"))
+}
+
+pub fn part2(input: &str) -> Result<u64, SolveError> {
+    lowest_location_of_ranges(&parse(input)?)
 }
 
 #[derive(Debug)]
-struct Map<'a> {
+pub struct Map<'a> {
     src: &'a str,
     dst: &'a str,
-    range_maps: Vec<RangeMap>,
+    ranges: IntervalMap<u64>,
 }
 
 impl<'a> Map<'a> {
     fn convert(&self, n: u64) -> u64 {
-        self.range_maps
-            .iter()
-            .filter_map(|m| m.convert(n))
-            .nth(0)
-            .unwrap_or(n)
-    }
-
-    fn convert_range_v2(&self, r: Range<u64>) -> Vec<Range<u64>> {
-        let mut converted = vec![];
-        let mut unchanged = vec![];
-        let (mut start, end) = (r.start, r.end);
-
-        let convert_ranges: Vec<(Range<u64>, Range<u64>)> = self
-            .range_maps
-            .iter()
-            .filter_map(|m| m.convert_range(r.clone()))
-            .sorted_by(|r1, r2| r1.0.start.cmp(&r2.0.start))
-            .collect();
-
-        if convert_ranges.is_empty() {
-            return vec![r];
-        }
-
-        for (src_r, dst_r) in convert_ranges.iter() {
-            converted.push(dst_r.clone());
-            if src_r.start > start {
-                unchanged.push(start..src_r.start);
-            }
-            start = src_r.end;
-        }
-        // end case
-        let (src_r, _) = convert_ranges.last().unwrap();
-        if src_r.end < end {
-            unchanged.push(src_r.end..end);
-        }
-        converted.extend(unchanged);
-        converted
+        self.ranges.get(n)
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct RangeMap {
-    src_range: Range<u64>,
-    dst_range: Range<u64>,
-}
-
-impl RangeMap {
-    fn convert(&self, n: u64) -> Option<u64> {
-        if self.src_range.contains(&n) {
-            return Some((n - self.src_range.start) + self.dst_range.start);
-        }
-        None
-    }
-
-    fn convert_range(
-        &self,
-        r: Range<u64>,
-    ) -> Option<(Range<u64>, Range<u64>)> {
-        // r: (start, end)
-        //    (dst_start, dst_end)
-        if (r.start).max(self.src_range.start)
-            <= (r.end).min(self.src_range.end)
-        {
-            let start = if self.src_range.start >= r.start {
-                self.src_range.start
-            } else {
-                r.start
-            };
-            let end = if self.src_range.end <= r.end {
-                self.src_range.end
-            } else {
-                r.end
-            };
-            let dst_start =
-                start - self.src_range.start + self.dst_range.start;
-            let dst_end = end + self.dst_range.end - self.src_range.end;
-            return Some((start..end, dst_start..dst_end));
-        }
-        None
-    }
-}
-
-fn parse_garden(input: &str) -> IResult<&str, (Vec<u64>, Vec<Map>)> {
-    let (input, seeds): (&str, Vec<u64>) = delimited(
-        tag("seeds: "),
-        separated_list1(space1, nom_u64),
-        line_ending,
-    )(input)?;
-
-    let (input, output) = many1(parse_map)(input)?;
-    Ok((input, (seeds, output)))
-}
-
-fn parse_map_title(input: &str) -> IResult<&str, (&str, &str), Error<&str>> {
+pub fn parse_garden(input: &str) -> IResult<&str, (Vec<u64>, Vec<Map<'_>>)> {
     separated_pair(
-        alpha1,
-        tag("-to-"),
-        terminated(alpha1, delimited(space1, tag("map:"), line_ending)),
+        preceded(tag("seeds: "), numbers(nom_u64)),
+        pair(line_ending, line_ending),
+        sections(parse_map),
     )(input)
 }
 
-fn parse_map(input: &str) -> IResult<&str, Map> {
-    let (input, (title, vecs)) = pair(
-        preceded(opt(line_ending), parse_map_title),
-        many1(terminated(separated_list1(space1, nom_u64), line_ending)),
-    )(input)?;
+fn parse_map_title(input: &str) -> IResult<&str, (&str, &str), Error<&str>> {
+    terminated(a_to_b, preceded(space1, tag("map:")))(input)
+}
 
-    let range_maps = vecs
-        .iter()
-        .map(|v| {
-            let mut it = v.iter();
-            let (dst, src, length) = (
-                *it.next().unwrap(),
-                *it.next().unwrap(),
-                *it.next().unwrap(),
-            );
-            RangeMap {
-                src_range: src..src + length,
-                dst_range: dst..dst + length,
-            }
-        })
-        .collect();
+pub fn parse_map(input: &str) -> IResult<&str, Map<'_>> {
+    let (input, ((src, dst), ranges)) =
+        separated_pair(parse_map_title, line_ending, lines(parse_range_map))(
+            input,
+        )?;
 
     Ok((
         input,
         Map {
-            src: title.0,
-            dst: title.1,
-            range_maps,
+            src,
+            dst,
+            ranges: ranges.into_iter().collect(),
         },
     ))
 }
 
-#[cfg(test)]
-mod tests {
-    use indoc::indoc;
-    use rstest::{fixture, rstest};
-
-    use super::*;
+/// One `dst src length` line: the source range and where it starts over.
+fn parse_range_map(input: &str) -> IResult<&str, (Range<u64>, u64)> {
+    map_opt(
+        tuple((
+            nom_u64,
+            preceded(space1, nom_u64),
+            preceded(space1, nom_u64),
+        )),
+        |(dst, src, length)| {
+            dst.checked_add(length)?;
+            Some((src..src.checked_add(length)?, dst))
+        },
+    )(input)
+}
 
-    #[fixture]
-    fn input() -> &'static str {
-        indoc! {
-            r#"
-            seeds: 79 14 55 13
+pub struct Day5;
 
-            seed-to-soil map:
-            50 98 2
-            52 50 48
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input<'a> = Almanac<'a>;
 
-            soil-to-fertilizer map:
-            0 15 37
-            37 52 2
-            39 0 15
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
 
-            fertilizer-to-water map:
-            49 53 8
-            0 11 42
-            42 0 7
-            57 7 4
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(lowest_location(input).into())
+    }
 
-            water-to-light map:
-            88 18 7
-            18 25 70
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        lowest_location_of_ranges(input).map(Answer::from)
+    }
+}
 
-            light-to-temperature map:
-            45 77 23
-            81 45 19
-            68 64 13
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
 
-            temperature-to-humidity map:
-            0 69 1
-            1 0 69
+    use super::*;
 
-            humidity-to-location map:
-            60 56 37
-            56 93 4
-            "#
-        }
-    }
+    const EXAMPLE: &str = include_str!("../examples/example1.txt");
 
     #[test]
     fn test_parse_map_title() {
         assert_eq!(
             parse_map_title("humidity-to-location map:\n").unwrap(),
-            ("", ("humidity", "location"))
+            ("\n", ("humidity", "location"))
         )
     }
 
@@ -275,31 +210,52 @@ mod tests {
             "#
         })
         .unwrap();
-        // println!("{:?}", output);
         assert_eq!(output.src, "humidity");
         assert_eq!(output.dst, "location");
+        assert_eq!(output.ranges.domain(), IntervalSet::from(56..97));
         assert_eq!(
-            vec![
-                RangeMap {
-                    dst_range: 60..97,
-                    src_range: 56..93,
-                },
-                RangeMap {
-                    dst_range: 56..60,
-                    src_range: 93..97,
-                },
-            ],
-            output.range_maps
-        )
+            [55, 56, 92, 93, 96, 97].map(|n| output.convert(n)),
+            [55, 60, 96, 56, 59, 97]
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Ok(35), part1(EXAMPLE))
     }
 
-    #[rstest]
-    fn test_part1(input: &str) {
-        assert_eq!(35, part1(input))
+    #[test]
+    fn test_part2() {
+        assert_eq!(Ok(46), part2(EXAMPLE))
     }
 
-    #[rstest]
-    fn test_part2(input: &str) {
-        assert_eq!(46, part2(input))
+    #[test]
+    fn test_parse_crlf() {
+        assert_eq!(Ok(35), part1(&EXAMPLE.replace('\n', "\r\n")))
+    }
+
+    #[test]
+    fn test_malformed_almanac() {
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98\n";
+        let err = part1(input).unwrap_err();
+        assert_eq!((err.line(), err.column()), (4, 6));
+        let input = "seeds: 79\n\nseed-to-soil map:\n50 98 2\n";
+        assert_eq!(part1(input), Ok(79));
+        let Err(SolveError::Malformed(err)) = part2(input) else {
+            panic!("should be malformed");
+        };
+        assert_eq!(
+            err.message(),
+            "expected seeds as pairs of start and length"
+        );
+        let input = "seeds: 79 0\n\nseed-to-soil map:\n50 98 2\n";
+        assert_eq!(
+            part2(input),
+            Err(SolveError::no_answer("the seed ranges hold no seed"))
+        );
+        let input =
+            "seeds: 79 14\n\nseed-to-soil map:\n1 18446744073709551615 2\n";
+        let err = part1(input).unwrap_err();
+        assert_eq!((err.line(), err.message()), (4, "number out of range"));
     }
 }
@@ -1,8 +1,13 @@
-use day5::{part1, part2, part2_brute_force};
+use common::embedded_input;
+use common::input::load_or_exit;
+use day5::{part1, part2};
 
 fn main() {
-    let input = include_str!("input.txt");
-    println!("Part1 Answer: {}", part1(input));
-    println!("Part2 Answer: {}", part2(input));
+    common::trace::init();
+    let input = &load_or_exit(embedded_input!());
+    let part1 = part1(input).unwrap_or_else(|err| err.exit());
+    println!("Part1 Answer: {}", part1);
+    let part2 = part2(input).unwrap_or_else(|err| err.exit());
+    println!("Part2 Answer: {}", part2);
     // println!("Part2 Answer: {}", part2_brute_force(input));
 }
@@ -0,0 +1,174 @@
+use std::iter::zip;
+
+use common::error::{ParseError, SolveError};
+use common::{Answer, Solution};
+use roots::find_roots_quadratic;
+use roots::Roots;
+
+/// Each race's time and record distance, and the numbers on each line as
+/// written, to join together for the single race.
+pub struct Races<'a> {
+    races: Vec<(u64, u64)>,
+    numbers: (&'a str, &'a str),
+}
+
+/// The numbers after `prefix` on `line`, and the text they're written in.
+fn parse_line<'a>(
+    input: &'a str,
+    line: Option<&'a str>,
+    prefix: &str,
+) -> Result<(Vec<u64>, &'a str), ParseError> {
+    let line = line.unwrap_or(&input[input.len()..]);
+    let numbers = line.strip_prefix(prefix).ok_or_else(|| {
+        ParseError::at_slice(input, line, format!("expected {}", prefix))
+    })?;
+    let parsed = numbers
+        .split_whitespace()
+        .map(|n| {
+            n.parse::<u64>().map_err(|_| {
+                ParseError::at_slice(input, n, "expected a number")
+            })
+        })
+        .collect::<Result<Vec<u64>, ParseError>>()?;
+    Ok((parsed, numbers))
+}
+
+pub fn parse(input: &str) -> Result<Races<'_>, ParseError> {
+    let mut lines = input.lines();
+    let (times, time) = parse_line(input, lines.next(), "Time:")?;
+    let distances = lines.next();
+    let (distances, distance) = parse_line(input, distances, "Distance:")?;
+    if times.len() != distances.len() {
+        let line = input.lines().nth(1).expect("should have distances");
+        return Err(ParseError::at_slice(
+            input,
+            line,
+            "expected as many distances as times",
+        ));
+    }
+    Ok(Races {
+        races: zip(times, distances).collect(),
+        numbers: (time, distance),
+    })
+}
+
+/// The product of the number of ways to win each race.
+fn margin(races: &Races) -> Result<usize, SolveError> {
+    races
+        .races
+        .iter()
+        .try_fold(1usize, |acc, &(time, distance)| {
+            acc.checked_mul(ways(time, distance))
+        })
+        .ok_or_else(|| SolveError::no_answer("too many ways to win to count"))
+}
+
+/// The number of ways to win the race the numbers make once joined.
+fn joined_ways(races: &Races) -> Result<usize, SolveError> {
+    let join = |numbers: &str| {
+        numbers
+            .split_whitespace()
+            .collect::<String>()
+            .parse::<u64>()
+            .map_err(|_| SolveError::no_answer("joined number out of range"))
+    };
+    Ok(ways(join(races.numbers.0)?, join(races.numbers.1)?))
+}
+
+fn ways(time: u64, distance: u64) -> usize {
+    compute_possible_ways(1f64, -(time as f64), distance as f64)
+}
+
+pub fn part1_and_part2(input: &str) -> Result<(usize, usize), SolveError> {
+    let races = parse(input)?;
+    Ok((margin(&races)?, joined_ways(&races)?))
+}
+
+fn compute_possible_ways(a: f64, b: f64, c: f64) -> usize {
+    let roots = find_roots_quadratic(a, b, c);
+    match roots {
+        Roots::Two(bound) => {
+            let (mut low, mut high) =
+                (*bound.first().unwrap(), *bound.last().unwrap());
+            if low.ceil() == low {
+                low = low.ceil() + 1.0;
+            }
+            if high.floor() == high {
+                high = high.floor() - 1.0;
+            }
+            (high.floor() as usize + 1).saturating_sub(low.ceil() as usize)
+        }
+        // The record can't be beaten.
+        _ => 0,
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input<'a> = Races<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(margin(input)?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(joined_ways(input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Part;
+
+    const EXAMPLE: &str = include_str!("../examples/example1.txt");
+
+    #[test]
+    fn test_get_ways() {
+        assert_eq!(compute_possible_ways(1f64, -7f64, 9f64), 4);
+        assert_eq!(compute_possible_ways(1f64, -15f64, 40f64), 8);
+        assert_eq!(compute_possible_ways(1f64, -30f64, 200f64), 9);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1_and_part2(EXAMPLE), Ok((288, 71503)))
+    }
+
+    #[test]
+    fn test_malformed_races() {
+        let err = parse("Time: 7 15\nDistance: 9\n").err().unwrap();
+        assert_eq!(err.message(), "expected as many distances as times");
+        let err = parse("Time: 7 1x\nDistance: 9 2\n").err().unwrap();
+        assert_eq!((err.line(), err.column()), (1, 9));
+        assert_eq!(part1_and_part2("Time: 2\nDistance: 9\n"), Ok((0, 0)));
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let input = "Time: 10000000000 10000000000\nDistance: 0 0\n";
+        assert_eq!(
+            Day6::solve(input, Part::Part1),
+            Err(SolveError::no_answer("too many ways to win to count"))
+        );
+        let input = format!(
+            "Time:{}\nDistance:{}\n",
+            " 10".repeat(11),
+            " 1".repeat(11)
+        );
+        assert_eq!(
+            Day6::solve(&input, Part::Part1),
+            Ok(Answer::from(9u64.pow(11)))
+        );
+        assert_eq!(
+            Day6::solve(&input, Part::Part2),
+            Err(SolveError::no_answer("joined number out of range"))
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use common::error::{ParseError, SolveError};
+use itertools::Itertools;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::iter::zip;
+use tracing::trace;
+
+/// Checks that every line is a hand of five cards followed by a bid.
+pub fn parse(input: &str) -> Result<&str, ParseError> {
+    for line in input.lines() {
+        let (label, bid) = line.split_once(' ').ok_or_else(|| {
+            ParseError::at_slice(input, &line[line.len()..], "expected a bid")
+        })?;
+        if let Some(i) = label.find(|c| !"23456789TJQKA".contains(c)) {
+            return Err(ParseError::at_slice(
+                input,
+                &label[i..],
+                "expected a card",
+            ));
+        }
+        if label.len() != 5 {
+            return Err(ParseError::at_slice(
+                input,
+                label,
+                "expected five cards",
+            ));
+        }
+        if bid.parse::<u32>().is_err() {
+            return Err(ParseError::at_slice(input, bid, "expected a number"));
+        }
+    }
+    Ok(input)
+}
+
+pub fn part1(input: &str) -> Result<u64, ParseError> {
+    let solution = Solution { part: Part::Part1 };
+    parse(input).map(|input| get_total_winnings(input, solution))
+}
+
+pub fn part2(input: &str) -> Result<u64, ParseError> {
+    let solution = Solution { part: Part::Part2 };
+    parse(input).map(|input| get_total_winnings(input, solution))
+}
+
+fn get_total_winnings(input: &str, solution: Solution) -> u64 {
+    input
+        .lines()
+        .map(|line| {
+            let (label, bid_str) = line.split_once(' ').unwrap();
+            solution.to_card(label, bid_str)
+        })
+        .sorted_by(|a, b| solution.compare(a, b))
+        .enumerate()
+        .map(|(rank, card)| {
+            trace!(rank = rank + 1, ?card);
+            (rank + 1) as u64 * u64::from(card.bid)
+        })
+        .sum()
+}
+
+#[derive(Debug)]
+struct Card<'a> {
+    label: &'a str,
+    bid: u32,
+    card_type: CardType,
+}
+
+#[derive(PartialEq, PartialOrd, Debug)]
+enum CardType {
+    FiveKind = 7,
+    FourKind = 6,
+    FullHouse = 5,
+    ThreeKind = 4,
+    TwoPair = 3,
+    OnePair = 2,
+    HighCard = 1,
+}
+
+enum Part {
+    Part1,
+    Part2,
+}
+
+struct Solution {
+    part: Part,
+}
+
+impl Solution {
+    fn compare(&self, a: &Card, b: &Card) -> Ordering {
+        let convert_char = |c: char| -> u32 {
+            if c.is_ascii_digit() {
+                return c.to_digit(10).unwrap();
+            }
+            match c {
+                'A' => 14,
+                'K' => 13,
+                'Q' => 12,
+                'J' => match self.part {
+                    Part::Part1 => 11,
+                    Part::Part2 => 1,
+                },
+                'T' => 10,
+                _ => unreachable!(),
+            }
+        };
+        let cmp_label = |a: &str, b: &str| -> Ordering {
+            for (c1, c2) in zip(a.chars(), b.chars()) {
+                let (d1, d2) = (convert_char(c1), convert_char(c2));
+                match d1.cmp(&d2) {
+                    Ordering::Equal => continue,
+                    order => return order,
+                }
+            }
+            Ordering::Equal
+        };
+
+        match a.card_type.partial_cmp(&b.card_type) {
+            Some(order) => match order {
+                Ordering::Equal => cmp_label(a.label, b.label),
+                _ => order,
+            },
+            None => unreachable!(),
+        }
+    }
+
+    fn to_card<'a>(&self, label: &'a str, bid_str: &'a str) -> Card<'a> {
+        Card {
+            label,
+            bid: bid_str.parse::<u32>().expect("should be a number"),
+            card_type: self.to_card_type(label),
+        }
+    }
+
+    fn to_card_type(&self, label: &str) -> CardType {
+        let mut counters: HashMap<char, usize> = label.chars().counts();
+        let count = match self.part {
+            Part::Part1 => {
+                let mut count: Vec<usize> = counters.into_values().collect();
+                count.sort();
+                count
+            }
+            Part::Part2 => {
+                let n_j = counters.remove(&'J').unwrap_or(0);
+                let mut count: Vec<usize> = counters.into_values().collect();
+                count.sort();
+                if count.is_empty() {
+                    count = vec![5];
+                } else {
+                    *count.last_mut().unwrap() += n_j;
+                }
+                count
+            }
+        };
+
+        match count[..] {
+            [5] => CardType::FiveKind,
+            [1, 4] => CardType::FourKind,
+            [2, 3] => CardType::FullHouse,
+            [1, 1, 3] => CardType::ThreeKind,
+            [1, 2, 2] => CardType::TwoPair,
+            [1, 1, 1, 2] => CardType::OnePair,
+            [1, 1, 1, 1, 1] => CardType::HighCard,
+            _ => unreachable!(),
+        }
+    }
+}
+
+pub struct Day7;
+
+impl common::Solution for Day7 {
+    const DAY: u8 = 7;
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<common::Answer, SolveError> {
+        let solution = Solution { part: Part::Part1 };
+        Ok(get_total_winnings(input, solution).into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<common::Answer, SolveError> {
+        let solution = Solution { part: Part::Part2 };
+        Ok(get_total_winnings(input, solution).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::{fixture, rstest};
+
+    const EXAMPLE: &str = include_str!("../examples/example1.txt");
+
+    #[fixture]
+    fn solution_part1() -> Solution {
+        Solution { part: Part::Part1 }
+    }
+
+    #[fixture]
+    fn solution_part2() -> Solution {
+        Solution { part: Part::Part2 }
+    }
+
+    #[test]
+    fn test_card_type() {
+        assert!(CardType::HighCard < CardType::FiveKind);
+        assert!(CardType::HighCard < CardType::FullHouse);
+        assert_eq!(CardType::HighCard, CardType::HighCard);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Ok(6440), part1(EXAMPLE));
+        assert_eq!(Ok(5905), part2(EXAMPLE));
+    }
+
+    #[test]
+    fn test_malformed_hand() {
+        let err = part1("32T3K 765\nT55X5 684\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 4));
+        assert_eq!(err.message(), "expected a card");
+        let err = part1("32T3K\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 6));
+    }
+
+    #[rstest]
+    fn test_order(solution_part1: Solution, solution_part2: Solution) {
+        assert_eq!(
+            solution_part1.compare(
+                &solution_part1.to_card("AAAA2", "12"),
+                &solution_part1.to_card("2222A", "1"),
+            ),
+            Ordering::Greater
+        );
+        assert_eq!(
+            solution_part2.compare(
+                &solution_part2.to_card("JJJJJ", "1"),
+                &solution_part2.to_card("2222A", "1"),
+            ),
+            Ordering::Greater
+        );
+        assert_eq!(
+            solution_part2.compare(
+                &solution_part2.to_card("JJJJJ", "1"),
+                &solution_part2.to_card("2JJJJ", "1"),
+            ),
+            Ordering::Less
+        )
+    }
+
+    #[rstest]
+    fn test_parse_card_type(solution_part1: Solution) {
+        assert_eq!(solution_part1.to_card_type("AAAAA"), CardType::FiveKind);
+        assert_eq!(solution_part1.to_card_type("AA8AA"), CardType::FourKind);
+        assert_eq!(solution_part1.to_card_type("23332"), CardType::FullHouse);
+        assert_eq!(solution_part1.to_card_type("TTT98"), CardType::ThreeKind);
+        assert_eq!(solution_part1.to_card_type("23432"), CardType::TwoPair);
+        assert_eq!(solution_part1.to_card_type("A23A4"), CardType::OnePair);
+        assert_eq!(solution_part1.to_card_type("23456"), CardType::HighCard);
+    }
+}
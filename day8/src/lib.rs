@@ -0,0 +1,449 @@
+use std::collections::HashMap;
+
+use common::error::{finish, ParseError, SolveError};
+use common::{Answer, Solution};
+use cycles::{Cycle, Orbit};
+use nom::character::complete::alphanumeric1;
+use nom::IResult;
+use parsers::{assignment, parenthesized_pair};
+
+#[derive(Clone, Copy)]
+enum Instruction {
+    Left,
+    Right,
+}
+
+impl TryFrom<char> for Instruction {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'L' => Ok(Instruction::Left),
+            'R' => Ok(Instruction::Right),
+            other => Err(other),
+        }
+    }
+}
+
+struct Network<'a> {
+    net: HashMap<&'a str, Node<'a>>,
+}
+
+impl<'a> Network<'a> {
+    /// Parses the node `lines`, slices of `input`, checking that every node
+    /// only leads to nodes in the network.
+    fn new(
+        input: &'a str,
+        lines: impl Iterator<Item = &'a str>,
+    ) -> Result<Network<'a>, ParseError> {
+        let network = lines
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| finish(input, parse_network(line)))
+            .map(|n| n.map(|n| (n.node_id, n)))
+            .collect::<Result<HashMap<&str, Node>, ParseError>>()?;
+        for node in network.values() {
+            for next in [node.left, node.right] {
+                if !network.contains_key(next) {
+                    return Err(ParseError::at_slice(
+                        input,
+                        next,
+                        format!("node {} is not in the network", next),
+                    ));
+                }
+            }
+        }
+
+        Ok(Network { net: network })
+    }
+}
+
+pub struct Documents<'a> {
+    instructions: Vec<Instruction>,
+    network: Network<'a>,
+}
+
+pub fn parse(input: &str) -> Result<Documents<'_>, ParseError> {
+    let mut lines = input.lines();
+    let ins = lines.next().unwrap_or_default().trim_end();
+    match lines.next() {
+        Some(blank) if blank.trim().is_empty() => {}
+        line => {
+            return Err(ParseError::at_slice(
+                input,
+                line.unwrap_or(&input[input.len()..]),
+                "expected a blank line after the instructions",
+            ))
+        }
+    }
+    if ins.is_empty() {
+        return Err(ParseError::at(input, 0, "expected instructions"));
+    }
+    let instructions = ins
+        .char_indices()
+        .map(|(i, c)| {
+            Instruction::try_from(c).map_err(|c| {
+                ParseError::at(input, i, format!("expected L or R, not {}", c))
+            })
+        })
+        .collect::<Result<Vec<Instruction>, ParseError>>()?;
+    let network = Network::new(input, lines)?;
+    Ok(Documents {
+        instructions,
+        network,
+    })
+}
+
+/// The number of steps from `curr` to a node ending in `Z`, or `None` if
+/// `curr` isn't in the network or never gets there.
+fn find_step<I: Iterator<Item = Instruction>>(
+    curr: &str,
+    ins_iter: I,
+    ins_len: usize,
+    network: &Network,
+) -> Option<u64> {
+    // Once every node has been seen at every instruction, we are in a loop.
+    let limit = ins_len * network.net.len();
+    let mut curr = curr;
+    for (step, ins) in ins_iter.enumerate().take(limit + 1) {
+        if curr.ends_with('Z') {
+            return Some(step as u64);
+        }
+        let node = network.net.get(curr)?;
+        curr = match ins {
+            Instruction::Left => node.left,
+            Instruction::Right => node.right,
+        };
+    }
+    None
+}
+
+/// The steps from `AAA`, failing when there is no way to `ZZZ`.
+fn steps_from_aaa(documents: &Documents) -> Result<u64, SolveError> {
+    let Some((&aaa, _)) = documents.network.net.get_key_value("AAA") else {
+        return Err(SolveError::no_answer("there is no node AAA"));
+    };
+    let ins_iter = documents.instructions.iter().copied().cycle();
+    let ins_len = documents.instructions.len();
+    find_step(aaa, ins_iter, ins_len, &documents.network)
+        .ok_or_else(|| SolveError::no_answer("no way from AAA to ZZZ"))
+}
+
+pub fn part1(input: &str) -> Result<u64, SolveError> {
+    steps_from_aaa(&parse(input)?)
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: u64, b: u64) -> Option<u64> {
+    (a / gcd(a, b).max(1)).checked_mul(b)
+}
+
+/// The inverse of `a` modulo `m`, for `a` and `m` coprime.
+fn inverse(a: i128, m: i128) -> i128 {
+    let (mut old_r, mut r) = (a.rem_euclid(m), m);
+    let (mut old_s, mut s) = (1, 0);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+    old_s.rem_euclid(m)
+}
+
+/// Combines `t ≡ r1 (mod m1)` and `t ≡ r2 (mod m2)` into one congruence,
+/// or `None` when they have no common solution or it overflows.
+fn crt((r1, m1): (u64, u64), (r2, m2): (u64, u64)) -> Option<(u64, u64)> {
+    let g = gcd(m1, m2);
+    if r1.abs_diff(r2) % g != 0 {
+        return None;
+    }
+    let m = lcm(m1, m2)?;
+    // Solve r1 + m1 * k ≡ r2 (mod m2) for k.
+    let (m1g, m2g) = ((m1 / g) as i128, (m2 / g) as i128);
+    let diff = (r2 as i128 - r1 as i128) / g as i128;
+    let k = (diff.rem_euclid(m2g) * inverse(m1g, m2g)).rem_euclid(m2g);
+    let r = (r1 as i128 + m1 as i128 * k).rem_euclid(m as i128);
+    Some((r as u64, m))
+}
+
+/// Where a ghost walking from `start` stands after each step, with the
+/// instruction it is about to follow, until it is back in a loop.
+fn walk<'a>(
+    start: &'a str,
+    documents: &Documents<'a>,
+) -> Orbit<(&'a str, usize)> {
+    let instructions = &documents.instructions;
+    let network = &documents.network.net;
+    Orbit::new((start, 0), |&(curr, i)| {
+        let node = &network[curr];
+        let next = match instructions[i] {
+            Instruction::Left => node.left,
+            Instruction::Right => node.right,
+        };
+        (next, (i + 1) % instructions.len())
+    })
+}
+
+/// The most combinations of `Z` steps `ghost_steps` keeps track of, as
+/// each ghost with several of them multiplies the ways they can line up.
+const MAX_CONGRUENCES: usize = 1 << 16;
+
+/// The steps until every ghost is on a `Z` node, failing when they never
+/// all get there (or the count overflows), or when there are more than
+/// `MAX_CONGRUENCES` ways for them to line up to search.
+///
+/// Each ghost ends up walking a loop, so past the longest lead-in the steps
+/// it is on a `Z` node are a few residues modulo its loop's length; the
+/// ghosts agree on the smallest step solving one residue of each.
+fn ghost_steps(documents: &Documents) -> Result<u64, SolveError> {
+    let starts: Vec<&str> = documents
+        .network
+        .net
+        .keys()
+        .copied()
+        .filter(|node_id| node_id.ends_with('A'))
+        .collect();
+    if starts.is_empty() {
+        return Err(SolveError::no_answer("there is no node ending in A"));
+    }
+    let never = || {
+        SolveError::no_answer("the ghosts are never all on a Z node at once")
+    };
+    let walks: Vec<_> =
+        starts.iter().map(|start| walk(start, documents)).collect();
+    let on_z =
+        |walk: &Orbit<(&str, usize)>, step| walk.at(step).0.ends_with('Z');
+
+    let lead_in = walks.iter().map(|w| w.cycle().start).max().unwrap_or(0);
+    if let Some(step) =
+        (0..lead_in).find(|&step| walks.iter().all(|w| on_z(w, step)))
+    {
+        return Ok(step as u64);
+    }
+
+    let mut congruences = vec![(0, 1)];
+    for walk in &walks {
+        let Cycle { start, length } = walk.cycle();
+        let residues: Vec<(u64, u64)> = (start..start + length)
+            .filter(|&step| on_z(walk, step))
+            .map(|step| ((step % length) as u64, length as u64))
+            .collect();
+        if congruences.len() * residues.len() > MAX_CONGRUENCES {
+            return Err(SolveError::no_answer(format!(
+                "more than {} ways for the ghosts to line up to search",
+                MAX_CONGRUENCES
+            )));
+        }
+        congruences = congruences
+            .iter()
+            .flat_map(|&a| residues.iter().filter_map(move |&b| crt(a, b)))
+            .collect();
+        // Residues with no common solution are dropped as they meet, so
+        // once none are left no later ghost can bring them back.
+        if congruences.is_empty() {
+            return Err(never());
+        }
+    }
+    congruences
+        .into_iter()
+        .filter_map(|(r, m)| {
+            let lead_in = lead_in as u64;
+            if r >= lead_in {
+                return Some(r);
+            }
+            let loops = (lead_in - r).div_ceil(m);
+            loops.checked_mul(m)?.checked_add(r)
+        })
+        .min()
+        .ok_or_else(never)
+}
+
+pub fn part2(input: &str) -> Result<u64, SolveError> {
+    ghost_steps(&parse(input)?)
+}
+
+pub struct Node<'a> {
+    node_id: &'a str,
+    left: &'a str,
+    right: &'a str,
+}
+
+pub fn parse_network(input: &str) -> IResult<&str, Node<'_>> {
+    let (input, (node_id, (left, right))) =
+        assignment(alphanumeric1, parenthesized_pair(alphanumeric1))(input)?;
+    Ok((
+        input,
+        Node {
+            node_id,
+            left,
+            right,
+        },
+    ))
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input<'a> = Documents<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        steps_from_aaa(input).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        ghost_steps(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    const EXAMPLE1: &str = include_str!("../examples/example1.txt");
+    const EXAMPLE2: &str = include_str!("../examples/example2.txt");
+    const EXAMPLE3: &str = include_str!("../examples/example3.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(Ok(2), part1(EXAMPLE1));
+        assert_eq!(Ok(6), part1(EXAMPLE2))
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE3), Ok(6));
+    }
+
+    #[test]
+    fn test_part2_unaligned_loops() {
+        // 11A is on 11Z at steps 2, 4, 6, ... and 22A at 1, 4, 7, ..., so
+        // the lcm of the first visits, 2, is not when both are there.
+        let input = indoc! {
+            r#"
+            L
+
+            11A = (11B, XXX)
+            11B = (11Z, XXX)
+            11Z = (11C, XXX)
+            11C = (11Z, XXX)
+            22A = (22Z, XXX)
+            22Z = (22B, XXX)
+            22B = (22C, XXX)
+            22C = (22Z, XXX)
+            XXX = (XXX, XXX)
+            "#
+        };
+
+        assert_eq!(part2(input), Ok(4));
+        assert_eq!(crt((2, 4), (1, 6)), None);
+        assert_eq!(crt((0, 2), (1, 3)), Some((4, 6)));
+    }
+
+    /// A ghost starting at `{name}0A` on a loop of `length` nodes, walked
+    /// with `L`, that is on a `Z` node at the steps in `zs`.
+    fn ghost(name: char, length: usize, zs: &[usize]) -> String {
+        let node = |i: usize| {
+            let end = match i {
+                0 => 'A',
+                i if zs.contains(&i) => 'Z',
+                _ => 'B',
+            };
+            format!("{}{}{}", name, i, end)
+        };
+        (0..length)
+            .map(|i| {
+                format!("{} = ({}, XXX)\n", node(i), node((i + 1) % length))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_part2_several_z_nodes() {
+        // On a Z node at odd steps, steps not divisible by 3, and steps
+        // 2 or 4 modulo 5: all three first agree on step 7.
+        let input = [
+            "L\n\nXXX = (XXX, XXX)\n".to_string(),
+            ghost('B', 4, &[1, 3]),
+            ghost('C', 3, &[1, 2]),
+            ghost('D', 5, &[2, 4]),
+        ]
+        .concat();
+        assert_eq!(part2(&input), Ok(7));
+
+        // One on Z nodes at odd steps only, one at even ones.
+        let input = [
+            "L\n\nXXX = (XXX, XXX)\n".to_string(),
+            ghost('B', 2, &[1]),
+            ghost('C', 4, &[2]),
+            ghost('D', 5, &[1, 2, 3, 4]),
+        ]
+        .concat();
+        assert_eq!(
+            part2(&input),
+            Err(SolveError::no_answer(
+                "the ghosts are never all on a Z node at once"
+            ))
+        );
+
+        // Loops of coprime lengths, so all 2 * 3 * 4 * 6 * 10 * 12 * 16
+        // ways to line up are kept.
+        let mut input = "L\n\nXXX = (XXX, XXX)\n".to_string();
+        for (name, length) in ('B'..).zip([3, 4, 5, 7, 11, 13, 17]) {
+            let zs: Vec<usize> = (1..length).collect();
+            input += &ghost(name, length, &zs);
+        }
+        let err = part2(&input).unwrap_err();
+        assert_eq!(
+            err,
+            SolveError::no_answer(
+                "more than 65536 ways for the ghosts to line up to search"
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_crlf() {
+        let input = "LR \r\n\r\nAAA = (ZZZ, ZZZ)\r\nZZZ = (ZZZ, ZZZ)\r\n";
+        assert_eq!(part1(input), Ok(1));
+    }
+
+    #[test]
+    fn test_no_way_out() {
+        let no_answer = |message| Err(SolveError::no_answer(message));
+        let input = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\n";
+        assert_eq!(part1(input), no_answer("no way from AAA to ZZZ"));
+        let input = "L\n\nBBB = (BBB, BBB)\n";
+        assert_eq!(part1(input), no_answer("there is no node AAA"));
+        assert_eq!(part2(input), no_answer("there is no node ending in A"));
+        let input = "L\n\nAAA = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n";
+        assert_eq!(
+            part2(input),
+            no_answer("the ghosts are never all on a Z node at once")
+        );
+    }
+
+    #[test]
+    fn test_malformed_network() {
+        let malformed =
+            |input| parse(input).err().expect("should be malformed");
+        let err = malformed("LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)\n");
+        assert_eq!(err.message(), "node BBB is not in the network");
+        let err = malformed("LX\n\nAAA = (AAA, AAA)\n");
+        assert_eq!((err.line(), err.column()), (1, 2));
+        let err = malformed("LR\n\nAAA = AAA, AAA)\n");
+        assert_eq!((err.line(), err.column()), (3, 7));
+    }
+}
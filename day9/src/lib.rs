@@ -0,0 +1,133 @@
+use common::error::{ParseError, SolveError};
+use common::{Answer, Solution};
+use itertools::Itertools;
+
+/// The rows of differences from `history` down to one of all zeros, or
+/// `None` if a difference doesn't fit in an `i64`.
+fn differences(history: &[i64]) -> Option<Vec<Vec<i64>>> {
+    let mut rows = vec![history.to_vec()];
+    while let Some(row) =
+        rows.last().filter(|row| !row.iter().all(|n| *n == 0))
+    {
+        let next = row
+            .iter()
+            .tuple_windows()
+            .map(|(a, b)| b.checked_sub(*a))
+            .collect::<Option<Vec<i64>>>()?;
+        rows.push(next);
+    }
+    Some(rows)
+}
+
+/// The value after the history the `rows` of differences come from.
+fn next(rows: &[Vec<i64>]) -> Option<i64> {
+    rows.iter()
+        .try_fold(0i64, |acc, v| acc.checked_add(*v.last().unwrap_or(&0)))
+}
+
+/// The value before the history the `rows` of differences come from.
+fn previous(rows: &[Vec<i64>]) -> Option<i64> {
+    rows.iter()
+        .rev()
+        .try_fold(0i64, |acc, v| v.first().unwrap_or(&0).checked_sub(acc))
+}
+
+/// Reads one non-empty history of numbers per line.
+pub fn parse(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let history = line
+                .split_whitespace()
+                .map(|n| {
+                    n.parse::<i64>().map_err(|_| {
+                        ParseError::at_slice(input, n, "expected a number")
+                    })
+                })
+                .collect::<Result<Vec<i64>, ParseError>>()?;
+            if history.is_empty() {
+                return Err(ParseError::at_slice(
+                    input,
+                    line,
+                    "expected a history",
+                ));
+            }
+            Ok(history)
+        })
+        .collect()
+}
+
+/// Sums the values `extrapolate` finds for each history, failing when one
+/// of them or the sum doesn't fit in an `i64`.
+fn sum(
+    histories: &[Vec<i64>],
+    extrapolate: fn(&[Vec<i64>]) -> Option<i64>,
+) -> Result<i64, SolveError> {
+    histories
+        .iter()
+        .enumerate()
+        .try_fold(0i64, |sum, (i, history)| {
+            let value = differences(history)
+                .as_deref()
+                .and_then(extrapolate)
+                .ok_or_else(|| {
+                    SolveError::no_answer(format!(
+                        "history {} extrapolates out of range",
+                        i + 1
+                    ))
+                })?;
+            sum.checked_add(value)
+                .ok_or_else(|| SolveError::no_answer("sum out of range"))
+        })
+}
+
+pub fn process(input: &str) -> Result<(i64, i64), SolveError> {
+    let histories = parse(input)?;
+    Ok((sum(&histories, next)?, sum(&histories, previous)?))
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Input<'a> = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(sum(input, next)?.into())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Answer, SolveError> {
+        Ok(sum(input, previous)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::Part;
+
+    const EXAMPLE: &str = include_str!("../examples/example1.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(process(EXAMPLE), Ok((114, 2)))
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let input = "0 3\n9223372036854775807 0\n";
+        assert_eq!(
+            Day9::solve(input, Part::Part1),
+            Ok(Answer::from(6 - i64::MAX))
+        );
+        assert_eq!(
+            Day9::solve(input, Part::Part2),
+            Err(SolveError::no_answer("history 2 extrapolates out of range"))
+        );
+        assert_eq!(process("5\n"), Ok((5, 5)));
+    }
+}
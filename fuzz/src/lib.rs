@@ -0,0 +1,9 @@
+use common::{Part, Solution};
+
+/// Solves both parts of `input`, which may be rejected as malformed but
+/// must never panic.
+pub fn solve<S: Solution>(input: &str) {
+    for part in [Part::Part1, Part::Part2] {
+        let _ = S::solve(input, part);
+    }
+}
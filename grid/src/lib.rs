@@ -0,0 +1,509 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// A cell position, `m` being the row and `n` the column.
+#[derive(
+    Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub struct Coord {
+    pub m: usize,
+    pub n: usize,
+}
+
+impl Coord {
+    pub const fn new(m: usize, n: usize) -> Coord {
+        Coord { m, n }
+    }
+
+    /// The coord one step away, or `None` when it would go below zero.
+    pub fn step(self, direction: Direction) -> Option<Coord> {
+        self.offset(direction.offset())
+    }
+
+    pub fn offset(self, (dm, dn): (isize, isize)) -> Option<Coord> {
+        Some(Coord {
+            m: self.m.checked_add_signed(dm)?,
+            n: self.n.checked_add_signed(dn)?,
+        })
+    }
+
+    pub fn manhattan(self, other: Coord) -> usize {
+        self.m.abs_diff(other.m) + self.n.abs_diff(other.n)
+    }
+}
+
+impl From<(usize, usize)> for Coord {
+    fn from((m, n): (usize, usize)) -> Self {
+        Coord { m, n }
+    }
+}
+
+impl Display for Coord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.m, self.n)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Clockwise, starting from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// The `(dm, dn)` offset of one step in this direction.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::East => (0, 1),
+            Direction::South => (1, 0),
+            Direction::West => (0, -1),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.turn_right().opposite()
+    }
+}
+
+const DIAGONALS: [(isize, isize); 4] = [(-1, -1), (-1, 1), (1, 1), (1, -1)];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid has no cells"),
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} cells, expected {}",
+                row, found, expected
+            ),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+/// A dense, row-major 2D grid.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Fails if `cells` does not fill `height` rows of `width`, or if the
+    /// rows have no columns.
+    pub fn from_vec(
+        cells: Vec<T>,
+        width: usize,
+        height: usize,
+    ) -> Result<Grid<T>, GridError> {
+        if width == 0 && height > 0 {
+            return Err(GridError::Empty);
+        }
+        if width * height != cells.len() {
+            return Err(GridError::Ragged {
+                row: cells.len() / width.max(1),
+                expected: width,
+                found: cells.len() % width.max(1),
+            });
+        }
+        Ok(Grid {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Fails on ragged rows, or rows with no columns.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, GridError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        if width == 0 && height > 0 {
+            return Err(GridError::Empty);
+        }
+        let mut cells = Vec::with_capacity(width * height);
+        for (m, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(GridError::Ragged {
+                    row: m,
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+            cells.extend(row);
+        }
+        Ok(Grid {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Parses one cell per char, one row per line. Trailing whitespace on
+    /// each line and trailing blank lines are ignored.
+    pub fn parse_with<F>(input: &str, mut f: F) -> Result<Grid<T>, GridError>
+    where
+        F: FnMut(Coord, char) -> T,
+    {
+        let rows: Vec<Vec<T>> = input
+            .trim_end()
+            .lines()
+            .map(str::trim_end)
+            .enumerate()
+            .map(|(m, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(n, ch)| f(Coord::new(m, n), ch))
+                    .collect()
+            })
+            .collect();
+        if rows.is_empty() {
+            return Err(GridError::Empty);
+        }
+        Grid::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.m < self.height && coord.n < self.width
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.contains(coord)
+            .then(|| &self.cells[coord.m * self.width + coord.n])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.contains(coord)
+            .then(|| &mut self.cells[coord.m * self.width + coord.n])
+    }
+
+    /// Panics if either coord is out of the grid, like indexing.
+    pub fn swap(&mut self, a: Coord, b: Coord) {
+        for coord in [a, b] {
+            assert!(self.contains(coord), "{} is out of the grid", coord);
+        }
+        self.cells
+            .swap(a.m * self.width + a.n, b.m * self.width + b.n);
+    }
+
+    /// All coords in row-major order.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> + Clone {
+        let width = self.width;
+        (0..self.height)
+            .flat_map(move |m| (0..width).map(move |n| Coord::new(m, n)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> + Clone + '_ {
+        self.coords().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter()
+    }
+
+    pub fn position<P>(&self, mut predicate: P) -> Option<Coord>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().find(|(_, t)| predicate(t)).map(|(c, _)| c)
+    }
+
+    pub fn row(&self, m: usize) -> &[T] {
+        &self.cells[m * self.width..(m + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, n: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(n < self.width, "column {} out of bounds", n);
+        self.cells.iter().skip(n).step_by(self.width)
+    }
+
+    pub fn columns(
+        &self,
+    ) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.width).map(|n| self.column(n))
+    }
+
+    /// The in-bounds neighbours north, east, south and west of `coord`.
+    pub fn neighbours4(
+        &self,
+        coord: Coord,
+    ) -> impl Iterator<Item = Coord> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| coord.step(d))
+            .filter(|c| self.contains(*c))
+    }
+
+    /// `neighbours4` plus the in-bounds diagonals.
+    pub fn neighbours8(
+        &self,
+        coord: Coord,
+    ) -> impl Iterator<Item = Coord> + '_ {
+        Direction::ALL
+            .map(Direction::offset)
+            .into_iter()
+            .chain(DIAGONALS)
+            .filter_map(move |offset| coord.offset(offset))
+            .filter(|c| self.contains(*c))
+    }
+
+    pub fn map<U, F>(&self, mut f: F) -> Grid<U>
+    where
+        F: FnMut(Coord, &T) -> U,
+    {
+        Grid {
+            cells: self.iter().map(|(c, t)| f(c, t)).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Panics if `width` is 0 but `height` is not, as such a grid would
+    /// have rows without cells.
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        assert!(width > 0 || height == 0, "{height} rows of no columns");
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn transpose(&self) -> Grid<T> {
+        Grid {
+            cells: self.columns().flatten().cloned().collect(),
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    pub fn rotate_clockwise(&self) -> Grid<T> {
+        Grid {
+            cells: self.columns().flat_map(|c| c.rev()).cloned().collect(),
+            width: self.height,
+            height: self.width,
+        }
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Grid<T> {
+        Grid {
+            cells: (0..self.width)
+                .rev()
+                .flat_map(|n| self.column(n))
+                .cloned()
+                .collect(),
+            width: self.height,
+            height: self.width,
+        }
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &Self::Output {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{} is out of the grid", coord))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut Self::Output {
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{} is out of the grid", coord))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, |_, ch| ch)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        indoc! {
+            r#"
+            abc
+            def
+            "#
+        }
+        .parse()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Coord::new(1, 2)], 'f');
+        assert_eq!(grid.get(Coord::new(2, 0)), None);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+        assert_eq!("abc \r\ndef\r\n\r\n".parse::<Grid<char>>(), Ok(grid));
+        assert_eq!(
+            "ab\nc\n".parse::<Grid<char>>(),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!("".parse::<Grid<char>>(), Err(GridError::Empty));
+        assert_eq!(Grid::<u8>::from_vec(vec![], 0, 2), Err(GridError::Empty));
+        assert_eq!(Grid::<u8>::from_rows(vec![vec![]]), Err(GridError::Empty));
+        assert_eq!(
+            Grid::<u8>::from_vec(vec![], 0, 0).unwrap().rows().count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_swap() {
+        let mut grid = grid();
+        grid.swap(Coord::new(0, 0), Coord::new(1, 2));
+        assert_eq!(grid.to_string(), "fbc\ndea\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "out of the grid")]
+    fn test_swap_out_of_bounds() {
+        grid().swap(Coord::new(0, 3), Coord::new(0, 0));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.rows().next_back().unwrap(), ['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(
+            grid.rotate_counter_clockwise().to_string(),
+            "cf\nbe\nad\n"
+        );
+        assert_eq!(
+            grid.rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise(),
+            grid
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbours4(Coord::new(0, 0)).collect::<Vec<_>>(),
+            vec![Coord::new(0, 1), Coord::new(1, 0)]
+        );
+        assert_eq!(grid.neighbours4(Coord::new(1, 1)).count(), 3);
+        assert_eq!(grid.neighbours8(Coord::new(0, 1)).count(), 5);
+        assert_eq!(grid.neighbours8(Coord::new(1, 2)).count(), 3);
+    }
+
+    #[test]
+    fn test_direction() {
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_right(), d);
+            assert_eq!(d.opposite().opposite(), d);
+        }
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Coord::new(0, 0).step(Direction::North), None);
+        assert_eq!(
+            Coord::new(0, 0).step(Direction::South),
+            Some(Coord::new(1, 0))
+        );
+    }
+}
@@ -0,0 +1,348 @@
+//! Sets of integers stored as half-open ranges, and maps shifting each range
+//! of a domain by its own offset, for puzzles pushing huge ranges of numbers
+//! through lookup tables.
+
+use std::ops::{Add, Range, Sub};
+
+/// A set of integers as sorted ranges, none empty and no two touching.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: vec![] }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet::default()
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The smallest integer in the set.
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn contains(&self, n: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= n);
+        self.ranges.get(i).is_some_and(|r| r.start <= n)
+    }
+
+    /// Adds `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        *self = self.ranges.iter().cloned().chain([range]).collect();
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        self.ranges.iter().chain(&other.ranges).cloned().collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = vec![];
+        while let (Some(a), Some(b)) =
+            (self.ranges.get(i), other.ranges.get(j))
+        {
+            let (start, end) = (a.start.max(b.start), a.end.min(b.end));
+            if start < end {
+                ranges.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// The integers of `self` not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = vec![];
+        let mut j = 0;
+        for range in &self.ranges {
+            let mut start = range.start;
+            // Skip the ranges of `other` wholly before this one.
+            while other.ranges.get(j).is_some_and(|b| b.end <= start) {
+                j += 1;
+            }
+            let mut k = j;
+            while let Some(b) = other.ranges.get(k) {
+                if b.start >= range.end {
+                    break;
+                }
+                if b.start > start {
+                    ranges.push(start..b.start);
+                }
+                start = start.max(b.end);
+                k += 1;
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// The integers below `at` and those from `at` up.
+    pub fn split_at(&self, at: T) -> (IntervalSet<T>, IntervalSet<T>) {
+        let (mut below, mut above) = (vec![], vec![]);
+        for range in &self.ranges {
+            if range.end <= at {
+                below.push(range.clone());
+            } else if range.start >= at {
+                above.push(range.clone());
+            } else {
+                below.push(range.start..at);
+                above.push(at..range.end);
+            }
+        }
+        (IntervalSet { ranges: below }, IntervalSet { ranges: above })
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        [range].into_iter().collect()
+    }
+}
+
+/// Coalesces any ranges, overlapping, touching, unsorted or empty.
+impl<T: Copy + Ord> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut sorted: Vec<Range<T>> =
+            iter.into_iter().filter(|r| r.start < r.end).collect();
+        sorted.sort_by_key(|r| r.start);
+        let mut ranges: Vec<Range<T>> = vec![];
+        for range in sorted {
+            match ranges.last_mut() {
+                Some(last) if range.start <= last.end => {
+                    last.end = last.end.max(range.end);
+                }
+                _ => ranges.push(range),
+            }
+        }
+        IntervalSet { ranges }
+    }
+}
+
+/// Shifts each of its source ranges to start at its own destination, and
+/// leaves the integers outside them where they are.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalMap<T> {
+    /// Sorted, disjoint source ranges with where each one's start goes.
+    pieces: Vec<(Range<T>, T)>,
+}
+
+impl<T> Default for IntervalMap<T> {
+    fn default() -> Self {
+        IntervalMap { pieces: vec![] }
+    }
+}
+
+impl<T> IntervalMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> IntervalMap<T> {
+        IntervalMap::default()
+    }
+
+    /// Sends `src` to the range starting at `dst`, except for the parts an
+    /// earlier insert already sends somewhere.
+    pub fn insert(&mut self, src: Range<T>, dst: T) {
+        let free = IntervalSet::from(src.clone()).difference(&self.domain());
+        for piece in free.ranges {
+            let to = dst + (piece.start - src.start);
+            self.pieces.push((piece, to));
+        }
+        self.pieces.sort_by_key(|(piece, _)| piece.start);
+    }
+
+    /// The integers the map moves.
+    pub fn domain(&self) -> IntervalSet<T> {
+        self.pieces.iter().map(|(piece, _)| piece.clone()).collect()
+    }
+
+    pub fn get(&self, n: T) -> T {
+        let i = self.pieces.partition_point(|(piece, _)| piece.end <= n);
+        match self.pieces.get(i) {
+            Some((piece, to)) if piece.start <= n => *to + (n - piece.start),
+            _ => n,
+        }
+    }
+
+    /// Where the integers of `set` go.
+    pub fn apply(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut ranges = set.difference(&self.domain()).ranges;
+        for (piece, to) in &self.pieces {
+            let moved = set.intersection(&IntervalSet::from(piece.clone()));
+            ranges.extend(moved.ranges.into_iter().map(|r| {
+                *to + (r.start - piece.start)..*to + (r.end - piece.start)
+            }));
+        }
+        ranges.into_iter().collect()
+    }
+}
+
+/// Inserts each `(src, dst)` in turn, so earlier ones win where they
+/// overlap.
+impl<T> FromIterator<(Range<T>, T)> for IntervalMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = (Range<T>, T)>>(iter: I) -> Self {
+        let mut map = IntervalMap::new();
+        for (src, dst) in iter {
+            map.insert(src, dst);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_set() {
+        let set: IntervalSet<u64> =
+            [5..8, 1..3, 3..4, 7..10, 12..12].into_iter().collect();
+        assert_eq!(set.ranges(), [1..4, 5..10]);
+        assert_eq!(set.min(), Some(1));
+        assert!(set.contains(3) && !set.contains(4) && set.contains(9));
+
+        let other = IntervalSet::from(2..6);
+        assert_eq!(set.union(&other), IntervalSet::from(1..10));
+        assert_eq!(set.intersection(&other).ranges(), [2..4, 5..6]);
+        assert_eq!(set.difference(&other).ranges(), [1..2, 6..10]);
+        let (below, above) = set.split_at(6);
+        assert_eq!(below.ranges(), [1..4, 5..6]);
+        assert_eq!(above, IntervalSet::from(6..10));
+    }
+
+    #[test]
+    fn test_map() {
+        // The first map of the day 5 example.
+        let map: IntervalMap<u64> =
+            [(98..100, 50), (50..98, 52)].into_iter().collect();
+        assert_eq!(
+            [79, 14, 55, 13, 98, 99].map(|n| map.get(n)),
+            [81, 14, 57, 13, 50, 51]
+        );
+        assert_eq!(map.domain(), IntervalSet::from(50..100));
+        let seeds: IntervalSet<u64> = [79..93, 55..68].into_iter().collect();
+        assert_eq!(map.apply(&seeds).ranges(), [57..70, 81..95]);
+
+        let first_wins: IntervalMap<u64> =
+            [(0..10, 100), (5..15, 200)].into_iter().collect();
+        assert_eq!(first_wins.get(7), 107);
+        assert_eq!(first_wins.get(12), 207);
+    }
+
+    const LIMIT: u32 = 64;
+
+    fn ranges() -> impl Strategy<Value = Vec<Range<u32>>> {
+        prop::collection::vec((0..LIMIT, 0..LIMIT), 0..8).prop_map(|pairs| {
+            pairs.into_iter().map(|(a, b)| a.min(b)..a.max(b)).collect()
+        })
+    }
+
+    fn model(ranges: &[Range<u32>]) -> BTreeSet<u32> {
+        ranges.iter().flat_map(|r| r.clone()).collect()
+    }
+
+    fn elements(set: &IntervalSet<u32>) -> BTreeSet<u32> {
+        model(set.ranges())
+    }
+
+    proptest! {
+        #[test]
+        fn prop_coalesced(ranges in ranges()) {
+            let set: IntervalSet<u32> = ranges.iter().cloned().collect();
+            prop_assert_eq!(elements(&set), model(&ranges));
+            for pair in set.ranges().windows(2) {
+                prop_assert!(pair[0].end < pair[1].start);
+            }
+            prop_assert!(set.ranges().iter().all(|r| r.start < r.end));
+            for n in 0..LIMIT {
+                prop_assert_eq!(set.contains(n), model(&ranges).contains(&n));
+            }
+        }
+
+        #[test]
+        fn prop_set_operations(a in ranges(), b in ranges(), at in 0..LIMIT) {
+            let (set_a, set_b): (IntervalSet<u32>, IntervalSet<u32>) =
+                (a.iter().cloned().collect(), b.iter().cloned().collect());
+            let (model_a, model_b) = (model(&a), model(&b));
+            prop_assert_eq!(
+                elements(&set_a.union(&set_b)),
+                &model_a | &model_b
+            );
+            prop_assert_eq!(
+                elements(&set_a.intersection(&set_b)),
+                &model_a & &model_b
+            );
+            prop_assert_eq!(
+                elements(&set_a.difference(&set_b)),
+                &model_a - &model_b
+            );
+            let (below, above) = set_a.split_at(at);
+            prop_assert_eq!(
+                elements(&below),
+                model_a.iter().copied().filter(|n| *n < at).collect::<BTreeSet<_>>()
+            );
+            prop_assert_eq!(
+                elements(&above),
+                model_a.iter().copied().filter(|n| *n >= at).collect::<BTreeSet<_>>()
+            );
+            let mut inserted = set_a.clone();
+            for range in &b {
+                inserted.insert(range.clone());
+            }
+            prop_assert_eq!(inserted, set_a.union(&set_b));
+        }
+
+        #[test]
+        fn prop_map(
+            table in prop::collection::vec((ranges(), 0..LIMIT), 0..4),
+            set in ranges(),
+        ) {
+            let table: Vec<(Range<u32>, u32)> = table
+                .into_iter()
+                .flat_map(|(srcs, dst)| srcs.into_iter().map(move |src| (src, dst)))
+                .collect();
+            let map: IntervalMap<u32> = table.iter().cloned().collect();
+            // The first entry whose source holds `n` moves it.
+            let lookup = |n: u32| {
+                table
+                    .iter()
+                    .find(|(src, _)| src.contains(&n))
+                    .map_or(n, |(src, dst)| dst + (n - src.start))
+            };
+            for n in 0..LIMIT {
+                prop_assert_eq!(map.get(n), lookup(n));
+            }
+            let set: IntervalSet<u32> = set.into_iter().collect();
+            prop_assert_eq!(
+                elements(&map.apply(&set)),
+                elements(&set).into_iter().map(lookup).collect::<BTreeSet<_>>()
+            );
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! nom combinators for the shapes puzzle inputs keep coming in: lists of
+//! numbers, `Label N:` prefixes, `a-to-b` headers, `X = (Y, Z)` tuples and
+//! sections separated by blank lines.
+//!
+//! They all use nom's default error, so their results go straight into
+//! `common::error::finish`.
+
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{
+    alpha1, i64 as nom_i64, line_ending, space0, space1, u32 as nom_u32,
+};
+use nom::combinator::eof;
+use nom::error::Error;
+use nom::multi::separated_list1;
+use nom::sequence::{
+    delimited, pair, preceded, separated_pair, terminated, tuple,
+};
+use nom::{IResult, Parser};
+
+/// Numbers separated by spaces, such as `79 14 55 13`. Extra spaces before
+/// a number are skipped, as in right-aligned columns like ` 1 21 53`.
+pub fn numbers<'a, O, F>(
+    number: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(space1, preceded(space0, number))
+}
+
+/// Numbers that may carry a sign, such as `0 -3 +6`.
+pub fn signed_numbers(input: &str) -> IResult<&str, Vec<i64>> {
+    numbers(nom_i64)(input)
+}
+
+/// The number `N` of a `Label N:` prefix, such as `Game 7:` or `Card  12:`.
+/// Whatever follows the colon is left to the caller.
+pub fn label_number<'a>(
+    label: &'static str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, u32> {
+    delimited(pair(tag(label), space1), nom_u32, tag(":"))
+}
+
+/// Both names of an `a-to-b` header, such as `seed-to-soil`.
+pub fn a_to_b(input: &str) -> IResult<&str, (&str, &str)> {
+    separated_pair(alpha1, tag("-to-"), alpha1)(input)
+}
+
+/// Two items in parentheses, such as `(BBB, CCC)`.
+pub fn parenthesized_pair<'a, O, F>(
+    item: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (O, O)>
+where
+    F: Parser<&'a str, O, Error<&'a str>> + Clone,
+{
+    delimited(
+        tag("("),
+        separated_pair(item.clone(), pair(tag(","), space0), item),
+        tag(")"),
+    )
+}
+
+/// A `key = value` line, such as `AAA = (BBB, CCC)`.
+pub fn assignment<'a, K, V, F, G>(
+    key: F,
+    value: G,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)>
+where
+    F: Parser<&'a str, K, Error<&'a str>>,
+    G: Parser<&'a str, V, Error<&'a str>>,
+{
+    separated_pair(key, tuple((space0, tag("="), space0)), value)
+}
+
+/// One or more lines of `line`, without the ending of the last one.
+pub fn lines<'a, O, F>(
+    line: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(line_ending, line)
+}
+
+/// One or more sections separated by blank lines. A section does not
+/// consume the ending of its last line; the ending of the last section is
+/// left to the caller.
+pub fn sections<'a, O, F>(
+    section: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(pair(line_ending, line_ending), section)
+}
+
+/// `parser` followed by a line ending, or by the end of the input.
+pub fn line<'a, O, F>(parser: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    terminated(parser, alt((line_ending, eof)))
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use nom::character::complete::{alphanumeric1, u64 as nom_u64};
+
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(numbers(nom_u64)("79 14 55"), Ok(("", vec![79, 14, 55])));
+        assert_eq!(
+            numbers(nom_u32)(" 1 21  3 | 4"),
+            Ok((" | 4", vec![1, 21, 3]))
+        );
+        assert_eq!(
+            signed_numbers("0 -3 +6 9\n"),
+            Ok(("\n", vec![0, -3, 6, 9]))
+        );
+        assert!(numbers(nom_u32)("x 1").is_err());
+        assert!(numbers(nom_u32)("-1").is_err());
+    }
+
+    #[test]
+    fn test_label_number() {
+        assert_eq!(label_number("Game")("Game 7: 3 red"), Ok((" 3 red", 7)));
+        assert_eq!(label_number("Card")("Card  12: 41"), Ok((" 41", 12)));
+        assert!(label_number("Card")("Card12: 41").is_err());
+        assert!(label_number("Card")("Game 1: 41").is_err());
+        assert!(label_number("Card")("Card 1 41").is_err());
+    }
+
+    #[test]
+    fn test_a_to_b() {
+        assert_eq!(
+            a_to_b("seed-to-soil map:"),
+            Ok((" map:", ("seed", "soil")))
+        );
+        assert!(a_to_b("seed-soil").is_err());
+    }
+
+    #[test]
+    fn test_assignment() {
+        let mut node =
+            assignment(alphanumeric1, parenthesized_pair(alphanumeric1));
+        assert_eq!(
+            node("AAA = (BBB, CCC)"),
+            Ok(("", ("AAA", ("BBB", "CCC"))))
+        );
+        assert_eq!(node("11A=(11B,XXX)"), Ok(("", ("11A", ("11B", "XXX")))));
+        assert!(node("AAA = (BBB CCC)").is_err());
+        assert!(node("AAA = BBB, CCC").is_err());
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = indoc! {"
+            1 2
+            3
+
+            4
+
+            5 6
+        "};
+        let mut parse = sections(lines(numbers(nom_u32)));
+        assert_eq!(
+            parse(input),
+            Ok((
+                "\n",
+                vec![
+                    vec![vec![1, 2], vec![3]],
+                    vec![vec![4]],
+                    vec![vec![5, 6]]
+                ]
+            ))
+        );
+        assert_eq!(
+            parse("1\r\n\r\n2"),
+            Ok(("", vec![vec![vec![1]], vec![vec![2]]]))
+        );
+        assert_eq!(parse("1\n\n\n2"), Ok(("\n\n\n2", vec![vec![vec![1]]])));
+    }
+
+    #[test]
+    fn test_line() {
+        assert_eq!(line(numbers(nom_u32))("1 2\n3"), Ok(("3", vec![1, 2])));
+        assert_eq!(line(numbers(nom_u32))("1 2"), Ok(("", vec![1, 2])));
+        assert!(line(numbers(nom_u32))("1 2 x").is_err());
+    }
+}
@@ -0,0 +1,379 @@
+//! Grid states drawn as images: each cell gets a fill colour, maybe a glyph
+//! and maybe an overlay dot, and the whole grid is written out as SVG, or as
+//! PNG with a square of pixels per cell and glyphs drawn as strokes.
+
+use std::fmt::{Display, Formatter, Write};
+use std::path::Path;
+use std::{fs, io};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    /// Black or white, whichever reads better on this colour.
+    pub fn contrast(self) -> Rgb {
+        let Rgb(r, g, b) = self;
+        let luma = 299 * r as u32 + 587 * g as u32 + 114 * b as u32;
+        if luma > 128_000 {
+            Rgb::BLACK
+        } else {
+            Rgb::WHITE
+        }
+    }
+}
+
+/// `#rrggbb`, as SVG takes it.
+impl Display for Rgb {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// How one cell is drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Style {
+    pub fill: Rgb,
+    /// Written over the fill. PNG strokes lines, mirrors and box drawing
+    /// characters and marks any other glyph with a small square.
+    pub glyph: Option<char>,
+    /// A dot over the middle of the cell, such as a rock or a marker.
+    pub overlay: Option<Rgb>,
+}
+
+impl Style {
+    pub const fn fill(fill: Rgb) -> Style {
+        Style {
+            fill,
+            glyph: None,
+            overlay: None,
+        }
+    }
+
+    pub const fn with_glyph(self, glyph: char) -> Style {
+        Style {
+            glyph: Some(glyph),
+            ..self
+        }
+    }
+
+    pub const fn with_overlay(self, overlay: Rgb) -> Style {
+        Style {
+            overlay: Some(overlay),
+            ..self
+        }
+    }
+}
+
+/// A grid that knows how to draw each of its cells. `cell` is the side of a
+/// cell in pixels wherever it is asked for.
+pub trait Picture {
+    /// The rows and columns of the grid.
+    fn size(&self) -> (usize, usize);
+
+    /// How to draw the cell in row `m` and column `n`.
+    fn style(&self, m: usize, n: usize) -> Style;
+
+    /// What the picture shows, kept in the SVG.
+    fn title(&self) -> String {
+        String::new()
+    }
+
+    fn to_svg(&self, cell: u32) -> String {
+        let (rows, cols) = self.size();
+        let cell = cell as usize;
+        let (width, height) = (cols * cell, rows * cell);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+             width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" \
+             font-size=\"{}\" text-anchor=\"middle\" \
+             dominant-baseline=\"central\">\n",
+            cell * 4 / 5
+        );
+        let title = self.title();
+        if !title.is_empty() {
+            writeln!(svg, "<title>{}</title>", escape(&title)).unwrap();
+        }
+        for m in 0..rows {
+            for n in 0..cols {
+                let style = self.style(m, n);
+                let (x, y) = (n * cell, m * cell);
+                let (cx, cy) = (
+                    x as f64 + cell as f64 / 2.0,
+                    y as f64 + cell as f64 / 2.0,
+                );
+                writeln!(
+                    svg,
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{cell}\" \
+                     height=\"{cell}\" fill=\"{}\"/>",
+                    style.fill
+                )
+                .unwrap();
+                if let Some(overlay) = style.overlay {
+                    writeln!(
+                        svg,
+                        "<circle cx=\"{cx}\" cy=\"{cy}\" r=\"{}\" \
+                         fill=\"{overlay}\"/>",
+                        radius(cell)
+                    )
+                    .unwrap();
+                }
+                if let Some(glyph) = style.glyph {
+                    let color = style.overlay.unwrap_or(style.fill).contrast();
+                    writeln!(
+                        svg,
+                        "<text x=\"{cx}\" y=\"{cy}\" \
+                         fill=\"{color}\">{}</text>",
+                        escape(&glyph.to_string())
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// The picture as an encoded PNG. Fails on an empty grid. Glyphs need a
+    /// few pixels, so cells under `MIN_GLYPH_CELL` leave them out.
+    fn to_png(&self, cell: u32) -> io::Result<Vec<u8>> {
+        let (rows, cols) = self.size();
+        let cell = cell as usize;
+        let (width, height) = (cols * cell, rows * cell);
+        let r = radius(cell);
+        let mut pixels = vec![0; width * height * 3];
+        for m in 0..rows {
+            for n in 0..cols {
+                let style = self.style(m, n);
+                let glyph = style.glyph.filter(|_| cell >= MIN_GLYPH_CELL);
+                let ink = style.overlay.unwrap_or(style.fill).contrast();
+                for dy in 0..cell {
+                    for dx in 0..cell {
+                        // Measured from the middle of the pixel.
+                        let (x, y) = (dx as f64 + 0.5, dy as f64 + 0.5);
+                        let (ox, oy) =
+                            (x - cell as f64 / 2.0, y - cell as f64 / 2.0);
+                        let Rgb(red, green, blue) = match style.overlay {
+                            _ if glyph
+                                .is_some_and(|g| inked(g, x, y, cell)) =>
+                            {
+                                ink
+                            }
+                            Some(overlay) if ox * ox + oy * oy <= r * r => {
+                                overlay
+                            }
+                            _ => style.fill,
+                        };
+                        let i = ((m * cell + dy) * width + n * cell + dx) * 3;
+                        pixels[i..i + 3].copy_from_slice(&[red, green, blue]);
+                    }
+                }
+            }
+        }
+        let mut png = vec![];
+        let mut encoder =
+            png::Encoder::new(&mut png, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&pixels))
+            .map_err(io::Error::other)?;
+        Ok(png)
+    }
+
+    /// Writes the picture to `path` as SVG or PNG, going by its extension.
+    fn save(&self, path: &Path, cell: u32) -> io::Result<()> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("svg") => fs::write(path, self.to_svg(cell)),
+            Some("png") => fs::write(path, self.to_png(cell)?),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{:?} should end in .svg or .png", path),
+            )),
+        }
+    }
+}
+
+/// The smallest cell, in pixels, that PNG draws glyphs in.
+pub const MIN_GLYPH_CELL: usize = 4;
+
+/// A line across a cell, its ends given as `(x, y)` fractions of the cell
+/// from its top left corner.
+type Stroke = ((f64, f64), (f64, f64));
+
+const TOP: (f64, f64) = (0.5, 0.0);
+const BOTTOM: (f64, f64) = (0.5, 1.0);
+const LEFT: (f64, f64) = (0.0, 0.5);
+const RIGHT: (f64, f64) = (1.0, 0.5);
+const MIDDLE: (f64, f64) = (0.5, 0.5);
+
+/// How PNG draws `glyph` without a font, or `None` for glyphs it only marks.
+fn strokes(glyph: char) -> Option<&'static [Stroke]> {
+    Some(match glyph {
+        ' ' => &[],
+        '|' | '│' => &[(TOP, BOTTOM)],
+        '-' | '─' => &[(LEFT, RIGHT)],
+        '+' | '┼' => &[(TOP, BOTTOM), (LEFT, RIGHT)],
+        '/' => &[((0.0, 1.0), (1.0, 0.0))],
+        '\\' => &[((0.0, 0.0), (1.0, 1.0))],
+        '└' => &[(TOP, MIDDLE), (MIDDLE, RIGHT)],
+        '┘' => &[(TOP, MIDDLE), (LEFT, MIDDLE)],
+        '┐' => &[(LEFT, MIDDLE), (MIDDLE, BOTTOM)],
+        '┌' => &[(MIDDLE, RIGHT), (MIDDLE, BOTTOM)],
+        _ => return None,
+    })
+}
+
+/// Whether the pixel centred at `(x, y)` of a cell of side `cell` belongs to
+/// `glyph`: within a stroke's width of one of its strokes, or inside the
+/// square marking a glyph without strokes.
+fn inked(glyph: char, x: f64, y: f64, cell: usize) -> bool {
+    let cell = cell as f64;
+    let Some(strokes) = strokes(glyph) else {
+        let half = cell / 8.0;
+        return (x - cell / 2.0).abs() <= half
+            && (y - cell / 2.0).abs() <= half;
+    };
+    let width = (cell / 8.0).max(1.0);
+    strokes.iter().any(|&((x0, y0), (x1, y1))| {
+        let (x0, y0, x1, y1) = (x0 * cell, y0 * cell, x1 * cell, y1 * cell);
+        // The nearest point of the stroke, `t` of the way along it.
+        let (sx, sy) = (x1 - x0, y1 - y0);
+        let t = (((x - x0) * sx + (y - y0) * sy) / (sx * sx + sy * sy))
+            .clamp(0.0, 1.0);
+        let (dx, dy) = (x - (x0 + t * sx), y - (y0 + t * sy));
+        dx * dx + dy * dy <= width * width / 4.0
+    })
+}
+
+/// The overlay dot's radius in a cell of side `cell`.
+fn radius(cell: usize) -> f64 {
+    cell as f64 * 0.3
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Rgb = Rgb(255, 0, 0);
+    const BLUE: Rgb = Rgb(0, 0, 255);
+
+    /// `#` cells red, with a blue dot on `o` and a glyph for anything else.
+    struct Text(Vec<&'static str>);
+
+    impl Picture for Text {
+        fn size(&self) -> (usize, usize) {
+            (self.0.len(), self.0[0].len())
+        }
+
+        fn style(&self, m: usize, n: usize) -> Style {
+            match self.0[m].as_bytes()[n] {
+                b'#' => Style::fill(RED),
+                b'o' => Style::fill(Rgb::WHITE).with_overlay(BLUE),
+                b'.' => Style::fill(Rgb::WHITE),
+                glyph => Style::fill(Rgb::WHITE).with_glyph(glyph as char),
+            }
+        }
+
+        fn title(&self) -> String {
+            "a & b".to_string()
+        }
+    }
+
+    #[test]
+    fn test_rgb() {
+        assert_eq!(Rgb(255, 136, 0).to_string(), "#ff8800");
+        assert_eq!(Rgb::WHITE.contrast(), Rgb::BLACK);
+        assert_eq!(BLUE.contrast(), Rgb::WHITE);
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = Text(vec!["#o", ".<"]).to_svg(10);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains(r#"width="20" height="20""#));
+        assert!(svg.contains("<title>a &amp; b</title>"));
+        assert_eq!(svg.matches("<rect ").count(), 4);
+        assert!(svg.contains(
+            r##"<rect x="0" y="0" width="10" height="10" fill="#ff0000"/>"##
+        ));
+        assert!(
+            svg.contains(r##"<circle cx="15" cy="5" r="3" fill="#0000ff"/>"##)
+        );
+        assert!(svg
+            .contains(r##"<text x="15" y="15" fill="#000000">&lt;</text>"##));
+    }
+
+    #[test]
+    fn test_png() {
+        let png = Text(vec!["#o", ".."]).to_png(10).unwrap();
+        let decoder = png::Decoder::new(png.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (20, 20));
+        let pixel = |x: usize, y: usize| {
+            let i = (y * 20 + x) * 3;
+            Rgb(pixels[i], pixels[i + 1], pixels[i + 2])
+        };
+        assert_eq!(pixel(0, 0), RED);
+        assert_eq!(pixel(9, 9), RED);
+        assert_eq!(pixel(15, 5), BLUE);
+        assert_eq!(pixel(10, 0), Rgb::WHITE);
+        assert_eq!(pixel(5, 15), Rgb::WHITE);
+        assert!(Text(vec![""]).to_png(10).is_err());
+    }
+
+    #[test]
+    fn test_png_glyphs() {
+        let png = Text(vec!["/x", "o|"]).to_png(8).unwrap();
+        let decoder = png::Decoder::new(png.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        let pixel = |x: usize, y: usize| {
+            let i = (y * 16 + x) * 3;
+            Rgb(pixels[i], pixels[i + 1], pixels[i + 2])
+        };
+        // The mirror runs from the bottom left to the top right.
+        assert_eq!(pixel(0, 7), Rgb::BLACK);
+        assert_eq!(pixel(4, 3), Rgb::BLACK);
+        assert_eq!(pixel(7, 0), Rgb::BLACK);
+        assert_eq!(pixel(0, 0), Rgb::WHITE);
+        assert_eq!(pixel(7, 7), Rgb::WHITE);
+        // Other glyphs get a square in the middle.
+        assert_eq!(pixel(12, 4), Rgb::BLACK);
+        assert_eq!(pixel(9, 1), Rgb::WHITE);
+        // The pipe runs down the middle of its cell.
+        assert_eq!(pixel(12, 8), Rgb::BLACK);
+        assert_eq!(pixel(12, 15), Rgb::BLACK);
+        assert_eq!(pixel(8, 12), Rgb::WHITE);
+        assert!(inked('─', 0.5, 4.0, 8) && !inked('─', 4.0, 0.5, 8));
+        assert!(inked('┌', 7.5, 4.0, 8) && !inked('┌', 0.5, 4.0, 8));
+    }
+
+    #[test]
+    fn test_save() {
+        let dir = std::env::temp_dir();
+        let name = format!("picture-{}", std::process::id());
+        let picture = Text(vec!["#o"]);
+        for extension in ["svg", "png"] {
+            let path = dir.join(format!("{name}.{extension}"));
+            picture.save(&path, 4).unwrap();
+            assert!(fs::metadata(&path).unwrap().len() > 0);
+            fs::remove_file(path).unwrap();
+        }
+        let err = picture.save(&dir.join(format!("{name}.gif")), 4);
+        assert_eq!(err.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,292 @@
+//! Graph searches over implicit graphs: the caller hands over a start node
+//! and a closure listing each node's neighbours, and gets back how far every
+//! node it reached is and the way there.
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// What a step between neighbours can cost: `Default` is the free step.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// The shortest ways a search found from its start to each node it reached.
+#[derive(Clone, Debug)]
+pub struct Paths<N, C> {
+    start: N,
+    /// Each node's distance and the node before it on its way.
+    reached: HashMap<N, (C, Option<N>)>,
+}
+
+impl<N: Hash + Eq + Clone, C: Cost> Paths<N, C> {
+    fn new(start: N) -> Paths<N, C> {
+        Paths {
+            reached: HashMap::from([(start.clone(), (C::default(), None))]),
+            start,
+        }
+    }
+
+    pub fn start(&self) -> &N {
+        &self.start
+    }
+
+    /// How far `node` is from the start, if it was reached.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.reached.get(node).map(|(distance, _)| *distance)
+    }
+
+    /// Every node reached with its distance, in no particular order.
+    pub fn distances(&self) -> impl Iterator<Item = (&N, C)> + '_ {
+        self.reached
+            .iter()
+            .map(|(node, (distance, _))| (node, *distance))
+    }
+
+    pub fn into_distances(self) -> HashMap<N, C> {
+        self.reached
+            .into_iter()
+            .map(|(node, (distance, _))| (node, distance))
+            .collect()
+    }
+
+    /// How many nodes were reached, the start included.
+    pub fn len(&self) -> usize {
+        self.reached.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reached.is_empty()
+    }
+
+    /// The node before `node` on its way from the start.
+    pub fn predecessor(&self, node: &N) -> Option<&N> {
+        self.reached.get(node)?.1.as_ref()
+    }
+
+    /// The nodes from the start to `node`, both included.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.reached.get(path.last()?)?.1.as_ref() {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth first search from `start`, each step costing one.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Paths<N, usize>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new(start.clone());
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let distance = paths.reached[&node].0 + 1;
+        for next in neighbours(&node) {
+            if let Entry::Vacant(entry) = paths.reached.entry(next) {
+                queue.push_back(entry.key().clone());
+                entry.insert((distance, Some(node.clone())));
+            }
+        }
+    }
+    paths
+}
+
+/// Dijkstra's algorithm from `start`, over every node it can reach.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+) -> Paths<N, C>
+where
+    N: Hash + Eq + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(start, neighbours, |_| C::default(), |_| false).1
+}
+
+/// A* from `start` to the nearest node passing `goal`, guided by a
+/// `heuristic` that never overestimates the cost left. Returns that node
+/// along with the paths searched so far, which are shortest for it.
+pub fn astar<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<(N, Paths<N, C>)>
+where
+    N: Hash + Eq + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    match best_first(start, neighbours, heuristic, goal) {
+        (Some(found), paths) => Some((found, paths)),
+        (None, _) => None,
+    }
+}
+
+/// Expands nodes cheapest estimate first until one passes `goal`.
+fn best_first<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (Option<N>, Paths<N, C>)
+where
+    N: Hash + Eq + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new(start.clone());
+    // Nodes live in `nodes` so the queue can order them without `N: Ord`.
+    let mut queue =
+        BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut nodes = vec![start];
+    while let Some(Reverse((_, distance, i))) = queue.pop() {
+        let node = nodes[i].clone();
+        if paths.reached[&node].0 < distance {
+            continue;
+        }
+        if goal(&node) {
+            return (Some(node), paths);
+        }
+        for (next, cost) in neighbours(&node) {
+            let next_distance = distance + cost;
+            if paths.distance(&next).is_some_and(|d| d <= next_distance) {
+                continue;
+            }
+            paths
+                .reached
+                .insert(next.clone(), (next_distance, Some(node.clone())));
+            queue.push(Reverse((
+                next_distance + heuristic(&next),
+                next_distance,
+                nodes.len(),
+            )));
+            nodes.push(next);
+        }
+    }
+    (None, paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The open cells of a maze, `#` being walls.
+    fn open(maze: &[&str], (m, n): (usize, usize)) -> Vec<(usize, usize)> {
+        let mut cells = vec![(m + 1, n), (m, n + 1)];
+        cells.extend(m.checked_sub(1).map(|m| (m, n)));
+        cells.extend(n.checked_sub(1).map(|n| (m, n)));
+        cells
+            .into_iter()
+            .filter(|&(m, n)| {
+                maze.get(m).and_then(|row| row.as_bytes().get(n))
+                    == Some(&b'.')
+            })
+            .collect()
+    }
+
+    const MAZE: [&str; 4] = ["...#", ".#..", ".#.#", "...."];
+
+    #[test]
+    fn test_bfs() {
+        let paths = bfs((0, 0), |&cell| open(&MAZE, cell));
+        assert_eq!(paths.distance(&(0, 0)), Some(0));
+        assert_eq!(paths.distance(&(1, 3)), Some(4));
+        assert_eq!(paths.distance(&(3, 3)), Some(6));
+        assert_eq!(paths.distance(&(1, 1)), None);
+        assert_eq!(paths.len(), 12);
+        assert_eq!(paths.predecessor(&(0, 1)), Some(&(0, 0)));
+        assert_eq!(
+            paths.path_to(&(2, 2)),
+            Some(vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)])
+        );
+        assert_eq!(paths.path_to(&(0, 0)), Some(vec![(0, 0)]));
+        assert_eq!(paths.path_to(&(0, 3)), None);
+    }
+
+    /// Steps into a cell cost its digit.
+    fn weighted(
+        grid: &[&str],
+        cell: (usize, usize),
+    ) -> Vec<((usize, usize), u32)> {
+        let digits: Vec<String> = grid
+            .iter()
+            .map(|row| row.replace(|c: char| c.is_ascii_digit(), "."))
+            .collect();
+        let rows: Vec<&str> = digits.iter().map(String::as_str).collect();
+        open(&rows, cell)
+            .into_iter()
+            .map(|(m, n)| {
+                let cost = grid[m].as_bytes()[n] - b'0';
+                ((m, n), cost as u32)
+            })
+            .collect()
+    }
+
+    const WEIGHTS: [&str; 3] = ["1911", "1919", "1111"];
+
+    #[test]
+    fn test_dijkstra() {
+        let paths = dijkstra((0, 0), |&cell| weighted(&WEIGHTS, cell));
+        // Round the bottom rather than through the 9s.
+        assert_eq!(paths.distance(&(0, 3)), Some(7));
+        assert_eq!(paths.distance(&(0, 1)), Some(9));
+        assert_eq!(
+            paths.path_to(&(1, 2)),
+            Some(vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (1, 2)])
+        );
+        assert_eq!(paths.len(), 12);
+    }
+
+    #[test]
+    fn test_astar() {
+        let goal = (0, 3);
+        let manhattan = |&(m, n): &(usize, usize)| {
+            (m.abs_diff(goal.0) + n.abs_diff(goal.1)) as u32
+        };
+        let (found, paths) = astar(
+            (0, 0),
+            |&cell| weighted(&WEIGHTS, cell),
+            manhattan,
+            |cell| *cell == goal,
+        )
+        .unwrap();
+        assert_eq!(found, goal);
+        assert_eq!(paths.distance(&goal), Some(7));
+        assert_eq!(paths.path_to(&goal).unwrap().len(), 8);
+        assert!(astar(
+            (0, 0),
+            |&cell| weighted(&WEIGHTS, cell),
+            |_| 0,
+            |cell| *cell == (5, 5)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_agree_with_bfs() {
+        // With unit costs Dijkstra and A* find what BFS does.
+        let unit = |cell: &(usize, usize)| {
+            open(&MAZE, *cell).into_iter().map(|c| (c, 1))
+        };
+        let by_bfs = bfs((0, 0), |&cell| open(&MAZE, cell));
+        let by_dijkstra = dijkstra((0, 0), unit);
+        assert_eq!(by_bfs.len(), by_dijkstra.len());
+        for (cell, distance) in by_bfs.distances() {
+            assert_eq!(by_dijkstra.distance(cell), Some(distance));
+            let (_, paths) =
+                astar((0, 0), unit, |_| 0, |c| c == cell).unwrap();
+            assert_eq!(paths.distance(cell), Some(distance));
+        }
+    }
+}